@@ -0,0 +1,162 @@
+use super::Icrp107;
+use crate::error::Error;
+use crate::primitive::attr::{NuclideMass, SpecificActivity};
+use crate::primitive::notation::atomic_number;
+use crate::primitive::Nuclide;
+
+/// Natural isotopic abundance (atom fraction) of primordial radionuclides, from the
+/// representative isotopic composition of IUPAC (2013)
+const NATURAL_ABUNDANCE: [(&str, f64); 21] = [
+    ("K-40", 0.000117),
+    ("V-50", 0.0025),
+    ("Rb-87", 0.2783),
+    ("Cd-113", 0.1222),
+    ("In-115", 0.9571),
+    ("Te-128", 0.3174),
+    ("Te-130", 0.3408),
+    ("La-138", 0.0008881),
+    ("Nd-144", 0.23798),
+    ("Sm-147", 0.1499),
+    ("Sm-148", 0.1124),
+    ("Gd-152", 0.0020),
+    ("Lu-176", 0.02599),
+    ("Hf-174", 0.0016),
+    ("Re-187", 0.6260),
+    ("Os-186", 0.0159),
+    ("Pt-190", 0.00012),
+    ("Th-232", 1.),
+    ("U-234", 0.000054),
+    ("U-235", 0.007204),
+    ("U-238", 0.992742),
+];
+
+/// Standard atomic weight of elements having primordial radionuclides, from IUPAC (2013)
+const ATOMIC_WEIGHT: [(&str, f64); 17] = [
+    ("K", 39.0983),
+    ("V", 50.9415),
+    ("Rb", 85.4678),
+    ("Cd", 112.414),
+    ("In", 114.818),
+    ("Te", 127.60),
+    ("La", 138.90547),
+    ("Nd", 144.242),
+    ("Sm", 150.36),
+    ("Gd", 157.25),
+    ("Lu", 174.9668),
+    ("Hf", 178.49),
+    ("Re", 186.207),
+    ("Os", 190.23),
+    ("Pt", 195.084),
+    ("Th", 232.0377),
+    ("U", 238.02891),
+];
+
+impl Icrp107 {
+    /// Natural isotopic abundance (atom fraction) of nuclide, if it is primordial
+    pub fn abundance(&self, nuclide: Nuclide) -> Option<f64> {
+        NATURAL_ABUNDANCE
+            .iter()
+            .find(|(n, _)| n.parse::<Nuclide>().is_ok_and(|n| n == nuclide))
+            .map(|&(_, x)| x)
+    }
+
+    /// Specific activity (Bq/g) of element symbol, e.g. "U", of natural isotopic composition
+    ///
+    /// Each indexed isotope contributes its specific activity times its mass fraction in
+    /// the element. Progeny are not included, and elements without primordial
+    /// radionuclides give zero.
+    pub fn element_specific_activity(&self, symbol: &str) -> Result<f64, Error> {
+        let mut isotopes = vec![];
+        for nuclide in self.isotopes_of(symbol)? {
+            if let Some(x) = self.abundance(nuclide) {
+                let activity = self.specific_activity(nuclide)?;
+                isotopes.push((x, self.nuclide_mass(nuclide)?, activity));
+            }
+        }
+
+        if isotopes.is_empty() {
+            return Ok(0.);
+        }
+        let weight = ATOMIC_WEIGHT
+            .iter()
+            .find(|(s, _)| atomic_number(s) == atomic_number(symbol))
+            .map(|&(_, w)| w)
+            .ok_or_else(|| Error::InvalidSymbol(symbol.to_string()))?;
+
+        Ok(natural_specific_activity(&isotopes, weight))
+    }
+}
+
+// Specific activity of element of atomic weight, from isotopes of (abundance, atomic mass,
+// specific activity)
+fn natural_specific_activity(isotopes: &[(f64, f64, f64)], atomic_weight: f64) -> f64 {
+    isotopes
+        .iter()
+        .map(|(x, mass, activity)| x * mass / atomic_weight * activity)
+        .sum()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const DATA_PATH: &str = "data/icrp107";
+
+    // Specific activity (Bq/g) of nuclide of half-life (y) and atomic mass
+    fn specific_activity(half_life_y: f64, mass: f64) -> f64 {
+        2f64.ln() / (half_life_y * 365.2422 * 86400.) * 6.022_140_76e23 / mass
+    }
+
+    #[test]
+    fn natural_potassium_and_uranium() {
+        // about 31.7 Bq/g of natural potassium
+        let k40 = (0.000117, 39.963998, specific_activity(1.248e9, 39.963998));
+        let k = natural_specific_activity(&[k40], 39.0983);
+        assert!((k / 31.7 - 1.).abs() < 0.02);
+
+        // about 25.3 kBq/g of natural uranium, nearly half of which is of U-234
+        let u = natural_specific_activity(
+            &[
+                (0.000054, 234.040952, specific_activity(2.455e5, 234.040952)),
+                (0.007204, 235.043930, specific_activity(7.04e8, 235.043930)),
+                (0.992742, 238.050788, specific_activity(4.468e9, 238.050788)),
+            ],
+            238.02891,
+        );
+        assert!((u / 25.3e3 - 1.).abs() < 0.02);
+
+        assert_eq!(natural_specific_activity(&[], 55.845), 0.);
+    }
+
+    #[test]
+    fn abundances() {
+        let total = |symbol: &str| -> f64 {
+            NATURAL_ABUNDANCE
+                .iter()
+                .filter(|(n, _)| n.parse::<Nuclide>().unwrap().z() == atomic_number(symbol))
+                .map(|(_, x)| x)
+                .sum()
+        };
+        assert!(total("U") <= 1. + 1e-12);
+        assert_eq!(total("Th"), 1.);
+        assert!(ATOMIC_WEIGHT
+            .iter()
+            .all(|&(s, _)| total(s) > 0. && total(s) <= 1. + 1e-12));
+    }
+
+    #[test]
+    #[ignore]
+    fn element_specific_activity_from_data() {
+        let data = Icrp107::open(DATA_PATH).unwrap();
+
+        assert_eq!(data.abundance("K-40".parse().unwrap()), Some(0.000117));
+        assert_eq!(data.abundance("Cs-137".parse().unwrap()), None);
+
+        let k = data.element_specific_activity("K").unwrap();
+        assert!((k / 31.7 - 1.).abs() < 0.02);
+        let u = data.element_specific_activity("U").unwrap();
+        assert!((u / 25.3e3 - 1.).abs() < 0.02);
+        assert_eq!(data.element_specific_activity("Fe").unwrap(), 0.);
+        assert!(data.element_specific_activity("Xx").is_err());
+    }
+}
@@ -0,0 +1,154 @@
+use float_pretty_print::PrettyPrintFloat;
+
+use super::spectrum::RadiationType;
+use super::Icrp107;
+use crate::error::Error;
+use crate::primitive::attr::{NuclideHalfLife, NuclideProgeny};
+use crate::primitive::{HalfLife, Nuclide, Progeny};
+
+/// Number of emission lines listed on a card
+const CARD_LINES: usize = 5;
+
+// Width of field names on a card
+const LABEL_WIDTH: usize = 11;
+
+/// Emission line of a card as (emitter, type, energy (MeV), yield (/nt of the nuclide))
+type CardLine = (Nuclide, RadiationType, f64, f64);
+
+impl Icrp107 {
+    /// Decay data of nuclide as a multi-line text card, e.g.
+    ///
+    /// ```text
+    /// Cs-137
+    /// Half-life  30.1671 y
+    /// Decay      β- 94.4% Ba-137m
+    ///            β- 5.6% Ba-137
+    /// Emissions  B- 0.174320 MeV 94.40%
+    ///            G  0.661657 MeV 84.87% (Ba-137m)
+    ///            ...
+    /// ```
+    ///
+    /// Strongest lines by yield are listed, including those of direct daughters with a
+    /// shorter half-life than nuclide, whose yields are scaled by the branch rate.
+    pub fn wallet_card(&self, nuclide: Nuclide) -> Result<String, Error> {
+        let half_life = self.half_life(nuclide)?;
+        let progeny = self.progeny(nuclide)?;
+
+        let mut lines: Vec<CardLine> = self
+            .rad_of(nuclide)?
+            .iter()
+            .map(|r| (nuclide, r.r#type, r.energy, r.r#yield.as_per_decay()))
+            .collect();
+
+        for daughter in &progeny {
+            let shorter_lived = self
+                .half_life(daughter.nuclide)
+                .is_ok_and(|hl| hl.as_sec() < half_life.as_sec());
+            if shorter_lived {
+                lines.extend(self.rad_of(daughter.nuclide)?.iter().map(|r| {
+                    (
+                        daughter.nuclide,
+                        r.r#type,
+                        r.energy,
+                        r.r#yield.as_per_decay() * daughter.branch_rate,
+                    )
+                }));
+            }
+        }
+
+        Ok(card_string(nuclide, &half_life, &progeny, &mut lines))
+    }
+}
+
+fn card_string(
+    nuclide: Nuclide,
+    half_life: &HalfLife,
+    progeny: &[Progeny],
+    lines: &mut [CardLine],
+) -> String {
+    let mut card = format!("{}\n", nuclide);
+    card.push_str(&format!(
+        "{:<width$}{}\n",
+        "Half-life",
+        half_life,
+        width = LABEL_WIDTH
+    ));
+
+    for (i, daughter) in progeny.iter().enumerate() {
+        let percent = PrettyPrintFloat(daughter.branch_rate * 100.).to_string();
+        card.push_str(&format!(
+            "{:<width$}{} {}% {}\n",
+            if i == 0 { "Decay" } else { "" },
+            daughter.decay_mode,
+            percent.strip_suffix(".0").unwrap_or(&percent),
+            daughter.nuclide,
+            width = LABEL_WIDTH
+        ));
+    }
+
+    lines.sort_by(|a, b| b.3.total_cmp(&a.3));
+    let type_width = lines
+        .iter()
+        .take(CARD_LINES)
+        .map(|l| l.1.to_string().len())
+        .max()
+        .unwrap_or(0);
+    for (i, &(emitter, r#type, energy, r#yield)) in lines.iter().take(CARD_LINES).enumerate() {
+        card.push_str(&format!(
+            "{:<width$}{:<type_width$} {:.6} MeV {:.2}%",
+            if i == 0 { "Emissions" } else { "" },
+            r#type.to_string(),
+            energy,
+            r#yield * 100.,
+            width = LABEL_WIDTH,
+            type_width = type_width
+        ));
+        if emitter != nuclide {
+            card.push_str(&format!(" ({})", emitter));
+        }
+        card.push('\n');
+    }
+
+    card
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::primitive::{DecayMode, DecayModeSet};
+
+    #[test]
+    fn card_layout() {
+        let cs137: Nuclide = "Cs-137".parse().unwrap();
+        let ba137m: Nuclide = "Ba-137m".parse().unwrap();
+        let progeny = [
+            Progeny {
+                nuclide: ba137m,
+                branch_rate: 0.944,
+                decay_mode: DecayModeSet::default() | DecayMode::BetaMinus,
+            },
+            Progeny {
+                nuclide: "Ba-137".parse().unwrap(),
+                branch_rate: 0.056,
+                decay_mode: DecayModeSet::default() | DecayMode::BetaMinus,
+            },
+        ];
+        let mut lines = [
+            (cs137, RadiationType::BetaMinus, 0.17432, 0.944),
+            (cs137, RadiationType::BetaMinus, 0.41635, 0.056),
+            (ba137m, RadiationType::Gamma, 0.661657, 0.899 * 0.944),
+        ];
+
+        let card = card_string(cs137, &"30.1671y".parse().unwrap(), &progeny, &mut lines);
+        let card: Vec<&str> = card.lines().collect();
+
+        assert_eq!(card.len(), 7);
+        assert_eq!(card[0], "Cs-137");
+        assert_eq!(card[1], "Half-life  30.1671 y");
+        assert_eq!(card[2], "Decay      β- 94.4% Ba-137m");
+        assert_eq!(card[3], "           β- 5.6% Ba-137");
+        assert_eq!(card[4], "Emissions  B- 0.174320 MeV 94.40%");
+        assert_eq!(card[5], "           G  0.661657 MeV 84.87% (Ba-137m)");
+        assert_eq!(card[6], "           B- 0.416350 MeV 5.60%");
+    }
+}
@@ -0,0 +1,1298 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::Write;
+
+use super::spectrum::{
+    diff, histogram, integrate_bins, nsf::NsfSpectrum, rad::RadSpectrum, sort_by_energy,
+    sum_energy, sum_yield, Histogram, RadiationType, SortOrder, SpectrumDiff,
+};
+use super::{Icrp107, MEV_TO_J};
+use crate::decaychain::DecaySeries;
+use crate::error::Error;
+use crate::primitive::attr::{AttenuationData, Energy};
+use crate::primitive::notation::Material;
+use crate::primitive::Nuclide;
+
+/// Tolerance (MeV) of matching the measured energy to photon lines
+const LINE_TOLERANCE_MEV: f64 = 1e-3;
+
+/// Header of photon spectrum in CSV
+const CSV_HEADER: &str = "energy_kev,yield,radiation_type";
+
+/// Density of dry air at 20 °C (kg/m3)
+const AIR_DENSITY: f64 = 1.205;
+
+/// Uncollided air kerma rate at 1 m above an infinite plane source, in Gy/s per
+/// Bq/m2 per MeV of photon energy emitted per decay
+///
+/// This is `E1(μh) / 2 · μen/ρ` of air, which varies within a few percent over
+/// 0.3-3 MeV and is taken as constant.
+const GROUND_PLANE_FACTOR: f64 = 9.5e-16;
+
+/// Photons below this energy (MeV) are excluded from air-kerma rate constant
+const AIR_KERMA_CUTOFF_MEV: f64 = 0.02;
+
+/// Mass energy-absorption coefficient (cm2/g) of dry air near sea level by photon
+/// energy (MeV), from the NIST tables of Hubbell and Seltzer
+const AIR_MU_EN: [(f64, f64); 28] = [
+    (0.01, 4.742),
+    (0.015, 1.334),
+    (0.02, 0.5389),
+    (0.03, 0.1537),
+    (0.04, 0.06833),
+    (0.05, 0.04098),
+    (0.06, 0.03041),
+    (0.08, 0.02407),
+    (0.1, 0.02325),
+    (0.15, 0.02496),
+    (0.2, 0.02672),
+    (0.3, 0.02872),
+    (0.4, 0.02949),
+    (0.5, 0.02966),
+    (0.6, 0.02953),
+    (0.8, 0.02882),
+    (1., 0.02789),
+    (1.25, 0.02666),
+    (1.5, 0.02547),
+    (2., 0.02345),
+    (3., 0.02057),
+    (4., 0.0187),
+    (5., 0.0174),
+    (6., 0.01647),
+    (8., 0.01525),
+    (10., 0.0145),
+    (15., 0.01353),
+    (20., 0.01311),
+];
+
+impl Icrp107 {
+    // Photon lines of nuclide as (energy (MeV), yield (/nt))
+    fn photon_lines(&self, nuclide: Nuclide) -> Result<Vec<(f64, f64)>, Error> {
+        Ok(self
+            .rad_of(nuclide)?
+            .iter()
+            .filter(|r| r.r#type.is_photon())
+            .map(|r| (r.energy, r.r#yield.as_per_decay()))
+            .collect())
+    }
+
+    /// Nuclides having alpha lines above `energy_mev`, with their highest alpha energy (MeV)
+    ///
+    /// Results are sorted by the highest alpha energy, in descending order.
+    pub fn nuclides_with_alpha_above(&self, energy_mev: f64) -> Result<Vec<(Nuclide, f64)>, Error> {
+        let mut nuclides = vec![];
+
+        for (&nuclide, spectrum) in self.rad()? {
+            let alpha_lines: Vec<(f64, f64)> = spectrum
+                .iter()
+                .filter(|r| r.r#type == RadiationType::Alpha)
+                .map(|r| (r.energy, r.r#yield.as_per_decay()))
+                .collect();
+
+            if let Some(energy) = highest_energy_above(&alpha_lines, energy_mev) {
+                nuclides.push((nuclide, energy));
+            }
+        }
+
+        nuclides.sort_by(|(a, a_e), (b, b_e)| b_e.total_cmp(a_e).then_with(|| a.cmp(b)));
+
+        Ok(nuclides)
+    }
+
+    /// Candidate nuclides for measured photon peaks, best matches first.
+    ///
+    /// Peaks are given as (energy (MeV), relative intensity), and a photon line matches
+    /// the nearest peak within `tol_mev`. Each nuclide is scored by the overlap of the
+    /// normalized intensities of peaks and lines, which is 1 for a perfect match.
+    /// Nuclides without any matched line are omitted.
+    pub fn identify(
+        &self,
+        peaks: &[(f64, f64)],
+        tol_mev: f64,
+    ) -> Result<Vec<(Nuclide, f64)>, Error> {
+        let mut candidates = vec![];
+
+        for &nuclide in self.rad()?.keys() {
+            let score = overlap_score(peaks, &self.photon_lines(nuclide)?, tol_mev);
+            if score > 0. {
+                candidates.push((nuclide, score));
+            }
+        }
+
+        candidates.sort_by(|(a, a_score), (b, b_score)| {
+            b_score.total_cmp(a_score).then_with(|| a.cmp(b))
+        });
+
+        Ok(candidates)
+    }
+
+    /// Photon spectrum of nuclide as a discrete probability distribution
+    ///
+    /// Lines are given as (energy (MeV), probability), where probabilities are yields
+    /// divided by the total photon yield and sum to 1.
+    pub fn photon_pdf(&self, nuclide: Nuclide) -> Result<Vec<(f64, f64)>, Error> {
+        let lines = self.photon_lines(nuclide)?;
+
+        if lines.is_empty() {
+            Err(Error::NoPhotonEmission(nuclide))
+        } else {
+            Ok(normalize(&lines))
+        }
+    }
+
+    /// Photon spectrum of nuclide as MCNP source definition cards (`SDEF`, `SI`, `SP`)
+    ///
+    /// Energies are discrete lines (MeV) and probabilities are normalized to unity.
+    pub fn rad_to_mcnp_source(&self, nuclide: Nuclide) -> Result<String, Error> {
+        Ok(mcnp_source(nuclide, &self.photon_pdf(nuclide)?))
+    }
+
+    /// Power (W) emitted as photons by given activity (Bq) of nuclide
+    pub fn gamma_power(&self, nuclide: Nuclide, activity_bq: f64) -> Result<f64, Error> {
+        Ok(activity_bq * photon_energy_per_decay(&self.photon_lines(nuclide)?) * MEV_TO_J)
+    }
+
+    /// Beta absorbed dose rate in air (Gy/s per Bq/m3) of immersion in a contaminated cloud
+    ///
+    /// The cloud is taken as a semi-infinite volume of air above ground, in which all beta
+    /// energy is absorbed locally, so the receptor at ground surface gets half of the dose
+    /// rate of an infinite cloud. Mean beta energy is the sum of yield times mean energy of
+    /// beta lines in RAD. Attenuation by dead layer of skin and clothing is neglected.
+    pub fn beta_immersion_coefficient(&self, nuclide: Nuclide) -> Result<f64, Error> {
+        let beta_energy = self
+            .rad_of(nuclide)?
+            .iter()
+            .filter(|r| {
+                matches!(
+                    r.r#type,
+                    RadiationType::BetaMinus | RadiationType::BetaPlus | RadiationType::DelayedBeta
+                )
+            })
+            .map(|r| r.energy * r.r#yield.as_per_decay())
+            .sum();
+
+        Ok(semi_infinite_cloud_dose_rate(beta_energy))
+    }
+
+    /// Air kerma rate (Gy/s) at 1 m above ground with surface contamination (Bq/m2)
+    ///
+    /// Ground is a smooth, infinite plane source under air, and the photons reaching the
+    /// receptor are uncollided, i.e. buildup in air and ground roughness are neglected.
+    /// The energy-independent factor overestimates the dose rate of photons below about
+    /// 100 keV. Photons of progeny (e.g. Ba-137m of Cs-137) are not included.
+    pub fn ground_contamination_dose_rate(
+        &self,
+        nuclide: Nuclide,
+        surface_bq_per_m2: f64,
+    ) -> Result<f64, Error> {
+        Ok(ground_plane_dose_rate(
+            &self.photon_lines(nuclide)?,
+            surface_bq_per_m2,
+        ))
+    }
+
+    /// Photon lines of nuclide as (energy (MeV), yield (/nt)) escaping a slab source
+    ///
+    /// Nuclide is uniform in a slab of `source_thickness_cm` of material, viewed along its
+    /// normal. Each yield is scaled by the slab average `(1 - exp(-µx)) / µx` of
+    /// uncollided photons, so scattered photons and oblique paths are neglected. It is an
+    /// error if `attenuation` has no coefficient at the energy of a line.
+    pub fn self_absorbed_spectrum(
+        &self,
+        nuclide: Nuclide,
+        source_thickness_cm: f64,
+        material: &Material,
+        attenuation: &dyn AttenuationData,
+    ) -> Result<Vec<(f64, f64)>, Error> {
+        self_absorbed(
+            &self.photon_lines(nuclide)?,
+            source_thickness_cm,
+            material,
+            attenuation,
+        )
+    }
+
+    /// Minimum detectable activity (Bq) of counting a photon line, by Currie's formula
+    ///
+    /// Detection limit is L_D = 2.71 + 4.65 sqrt(B) counts for background counts B, and
+    /// yields of photon lines within 1 keV of `energy_mev` are summed.
+    pub fn minimum_detectable_activity(
+        &self,
+        nuclide: Nuclide,
+        energy_mev: f64,
+        background_cps: f64,
+        count_time_s: f64,
+        efficiency: f64,
+    ) -> Result<f64, Error> {
+        if !(background_cps.is_finite() && background_cps >= 0.) {
+            return Err(Error::InvalidCountRate(format!("{} cps", background_cps)));
+        }
+        if !(count_time_s.is_finite() && count_time_s > 0.) {
+            return Err(Error::InvalidTime(format!("count time {} s", count_time_s)));
+        }
+        if !(efficiency > 0. && efficiency <= 1.) {
+            return Err(Error::InvalidEfficiency(efficiency.to_string()));
+        }
+
+        let photon_yield: f64 = self
+            .photon_lines(nuclide)?
+            .iter()
+            .filter(|(e, _)| (e - energy_mev).abs() <= LINE_TOLERANCE_MEV)
+            .map(|(_, y)| y)
+            .sum();
+
+        if photon_yield > 0. {
+            Ok(currie_mda(
+                background_cps,
+                count_time_s,
+                efficiency * photon_yield,
+            ))
+        } else {
+            Err(Error::NoPhotonLine(
+                nuclide,
+                (energy_mev * 1e6).round() as Energy,
+            ))
+        }
+    }
+
+    /// Line of maximum yield as (energy (MeV), yield (/nt)) for each radiation type present
+    pub fn peak_lines_by_type(
+        &self,
+        nuclide: Nuclide,
+    ) -> Result<HashMap<RadiationType, (f64, f64)>, Error> {
+        Ok(peak_lines(self.rad_of(nuclide)?))
+    }
+
+    /// Probability that a decay emits at least one photon above `threshold_mev`
+    ///
+    /// Coincidences of photons are not tabulated, so this is estimated by the expected
+    /// number of photons above threshold per decay (sum of yields), capped at 1. It is
+    /// an upper bound of the probability, and exact if at most one photon is emitted.
+    pub fn detectable_fraction(&self, nuclide: Nuclide, threshold_mev: f64) -> Result<f64, Error> {
+        Ok(detectable_fraction(
+            &self.photon_lines(nuclide)?,
+            threshold_mev,
+        ))
+    }
+
+    /// Photon lines of nuclide with yield (/nt) of at least `min_yield`, as (energy, yield)
+    ///
+    /// Lines are sorted by yield in descending order. X-rays are included only if
+    /// `with_x_rays` is set.
+    pub fn gamma_lines(
+        &self,
+        nuclide: Nuclide,
+        min_yield: f64,
+        with_x_rays: bool,
+    ) -> Result<Vec<(Energy, f64)>, Error> {
+        Ok(gamma_lines(self.rad_of(nuclide)?, min_yield, with_x_rays))
+    }
+
+    /// Photon line of nuclide of the highest yield (/nt), as (energy, yield)
+    ///
+    /// X-rays are included, and ties are broken by higher energy. It is `None` if nuclide
+    /// emits no photon. Lines of progeny are not included, e.g. the 661.7 keV gamma of
+    /// Cs-137 is tabulated for Ba-137m.
+    pub fn dominant_gamma(&self, nuclide: Nuclide) -> Result<Option<(Energy, f64)>, Error> {
+        let photons = self
+            .rad_of(nuclide)?
+            .iter()
+            .filter(|r| r.r#type.is_photon());
+        Ok(dominant_line(photons).map(|(_, energy, y)| (energy, y)))
+    }
+
+    /// Line of nuclide of the highest yield (/nt) of any radiation type, as (type, energy,
+    /// yield)
+    ///
+    /// Ties are broken by higher energy. It is `None` if nuclide is absent from RAD.
+    pub fn dominant_emission(
+        &self,
+        nuclide: Nuclide,
+    ) -> Result<Option<(RadiationType, Energy, f64)>, Error> {
+        Ok(dominant_line(self.rad_of(nuclide)?.iter()))
+    }
+
+    /// Photon lines, including X-rays, of parent and its progeny in secular equilibrium
+    ///
+    /// Yields of each member are weighted by its activity relative to the parent, which is
+    /// the cumulative branching fraction along the chain, so they are per decay of the
+    /// parent. Lines of the same energy are merged before dropping those below
+    /// `min_yield`, and sorted by yield in descending order. Stable members contribute
+    /// nothing.
+    pub fn chain_gamma_lines(
+        &self,
+        parent: Nuclide,
+        min_yield: f64,
+    ) -> Result<Vec<(Energy, f64)>, Error> {
+        let ndx = self.ndx()?;
+        let mut members = vec![];
+        for (nuclide, activity) in self.cumulative_yields(parent)? {
+            if ndx.contains_key(&nuclide) {
+                members.push((activity, gamma_lines(self.rad_of(nuclide)?, 0., true)));
+            }
+        }
+        Ok(merge_lines(&members, min_yield))
+    }
+
+    /// Energy (eV) emitted per decay as given type of radiation
+    ///
+    /// This is the sum of yield times energy of RAD lines of the type, e.g. gamma excludes
+    /// X-ray, prompt and delayed gamma lines. Nuclides absent from RAD emit nothing.
+    pub fn mean_energy(&self, nuclide: Nuclide, radiation: RadiationType) -> Result<Energy, Error> {
+        let energy = sum_energy(self.rad_of(nuclide)?, radiation);
+        Ok((energy * 1e6).round() as Energy)
+    }
+
+    /// Total yield (/nt) and energy (MeV) emitted per decay for each radiation type of
+    /// nuclide, as (yield, energy)
+    ///
+    /// Only types having RAD lines are present, so nuclides absent from RAD give an
+    /// empty summary.
+    pub fn emission_summary(
+        &self,
+        nuclide: Nuclide,
+    ) -> Result<HashMap<RadiationType, (f64, f64)>, Error> {
+        Ok(emission_summary(self.rad_of(nuclide)?))
+    }
+
+    /// Energy (MeV) emitted per decay by all RAD lines of nuclide
+    pub fn total_energy_per_decay(&self, nuclide: Nuclide) -> Result<f64, Error> {
+        Ok(energy_per_decay(self.rad_of(nuclide)?.iter()))
+    }
+
+    /// Write photon lines of nuclide as CSV rows of `energy_kev,yield,radiation_type`
+    ///
+    /// Rows are sorted by energy, and only the header is written if nuclide emits no
+    /// photon.
+    pub fn export_spectrum_csv(
+        &self,
+        nuclide: Nuclide,
+        mut writer: impl Write,
+    ) -> Result<(), Error> {
+        writeln!(writer, "{}", CSV_HEADER)?;
+        write_csv_rows(&mut writer, "", self.rad_of(nuclide)?)
+    }
+
+    /// Write photon lines of nuclides as CSV rows with a leading `nuclide` column
+    pub fn export_spectra_csv(
+        &self,
+        nuclides: &[Nuclide],
+        mut writer: impl Write,
+    ) -> Result<(), Error> {
+        writeln!(writer, "nuclide,{}", CSV_HEADER)?;
+        for &nuclide in nuclides {
+            write_csv_rows(&mut writer, &format!("{},", nuclide), self.rad_of(nuclide)?)?;
+        }
+        Ok(())
+    }
+
+    /// Mean energy (MeV) of beta particles, averaged over the BET spectrum
+    ///
+    /// Number density is linearly interpolated between grid points, which may be
+    /// non-uniform. Nuclides without BET spectrum give zero.
+    pub fn beta_mean_energy(&self, nuclide: Nuclide) -> Result<f64, Error> {
+        Ok(beta_mean_energy(&self.beta_points(nuclide)?))
+    }
+
+    /// Endpoint energy (MeV) of beta spectrum, which is its highest grid point
+    ///
+    /// Nuclides without BET spectrum give zero.
+    pub fn beta_endpoint_energy(&self, nuclide: Nuclide) -> Result<f64, Error> {
+        Ok(self
+            .bet_of(nuclide)?
+            .iter()
+            .map(|b| b.energy)
+            .fold(0., f64::max))
+    }
+
+    // BET grid points of nuclide as (energy (MeV), number (/MeV/nt))
+    pub(super) fn beta_points(&self, nuclide: Nuclide) -> Result<Vec<(f64, f64)>, Error> {
+        Ok(self
+            .bet_of(nuclide)?
+            .iter()
+            .map(|b| (b.energy, b.number))
+            .collect())
+    }
+
+    /// Air-kerma rate constant (µGy·m²/(MBq·h)) of the photon lines of nuclide
+    ///
+    /// This is `Σ y E (μen/ρ)(E) / 4π` over photon lines above 20 keV, with the mass
+    /// energy-absorption coefficient of air interpolated on log-log scale. Progeny are
+    /// not included, e.g. the constant of Cs-137 excludes the gamma of Ba-137m.
+    pub fn air_kerma_rate_constant(&self, nuclide: Nuclide) -> Result<f64, Error> {
+        air_kerma_rate_constant(&self.photon_lines(nuclide)?)
+    }
+
+    /// Photon lines, including X-rays, binned into ascending `bin_edges` (eV)
+    pub fn binned_gamma(&self, nuclide: Nuclide, bin_edges: &[f64]) -> Result<Histogram, Error> {
+        Ok(histogram(
+            &gamma_lines(self.rad_of(nuclide)?, 0., true),
+            bin_edges,
+        ))
+    }
+
+    /// Differences of photon lines, including X-rays, of nuclide `y` from nuclide `x`
+    ///
+    /// Lines are matched within 1 keV, see [`diff`](super::spectrum::diff).
+    pub fn diff_gamma(&self, x: Nuclide, y: Nuclide) -> Result<SpectrumDiff, Error> {
+        Ok(diff(
+            &gamma_lines(self.rad_of(x)?, 0., true),
+            &gamma_lines(self.rad_of(y)?, 0., true),
+            LINE_TOLERANCE_MEV * 1e6,
+        ))
+    }
+
+    /// Beta particles per decay in each bin of ascending `bin_edges` (eV), integrated
+    /// from BET grid points
+    pub fn binned_beta(&self, nuclide: Nuclide, bin_edges: &[f64]) -> Result<Vec<f64>, Error> {
+        let points: Vec<(f64, f64)> = self
+            .bet_of(nuclide)?
+            .iter()
+            .map(|b| (b.energy * 1e6, b.number * 1e-6))
+            .collect();
+        Ok(integrate_bins(&points, bin_edges))
+    }
+
+    /// Neutrons emitted per decay, which is the sum of yields of NSF bins
+    ///
+    /// Nuclides without NSF spectrum give zero.
+    pub fn neutron_yield(&self, nuclide: Nuclide) -> Result<f64, Error> {
+        Ok(self
+            .nsf_of(nuclide)?
+            .iter()
+            .map(|n| n.r#yield.as_per_decay())
+            .sum())
+    }
+
+    /// Mean energy (MeV) of neutrons, averaged over the NSF spectrum
+    ///
+    /// Neutrons of each bin are taken at the center of the bin. Nuclides without NSF
+    /// spectrum give zero.
+    pub fn neutron_mean_energy(&self, nuclide: Nuclide) -> Result<f64, Error> {
+        Ok(neutron_mean_energy(self.nsf_of(nuclide)?))
+    }
+}
+
+fn gamma_lines(spectrum: &[RadSpectrum], min_yield: f64, with_x_rays: bool) -> Vec<(Energy, f64)> {
+    let mut lines: Vec<(Energy, f64)> = spectrum
+        .iter()
+        .filter(|r| r.r#type.is_photon() && (with_x_rays || r.r#type != RadiationType::X))
+        .filter(|r| r.r#yield.as_per_decay() >= min_yield)
+        .map(|r| ((r.energy * 1e6).round() as Energy, r.r#yield.as_per_decay()))
+        .collect();
+    lines.sort_by(|a, b| b.1.total_cmp(&a.1));
+    lines
+}
+
+// Line of the highest yield as (type, energy, yield), or of the higher energy if tied
+fn dominant_line<'a>(
+    spectrum: impl Iterator<Item = &'a RadSpectrum>,
+) -> Option<(RadiationType, Energy, f64)> {
+    spectrum
+        .map(|r| {
+            let energy = (r.energy * 1e6).round() as Energy;
+            (r.r#type, energy, r.r#yield.as_per_decay())
+        })
+        .max_by(|a, b| a.2.total_cmp(&b.2).then(a.1.cmp(&b.1)))
+}
+
+// Lines of (relative activity, lines) weighted by activity, with yields of the same energy
+// summed. Energies are keyed exactly, as lines of the same MeV in RAD round to the same eV;
+// use `EnergyTolerance::round_to` beforehand to merge lines of close energies.
+fn merge_lines(members: &[(f64, Vec<(Energy, f64)>)], min_yield: f64) -> Vec<(Energy, f64)> {
+    let mut merged: BTreeMap<Energy, f64> = BTreeMap::new();
+    for (activity, lines) in members {
+        for &(energy, y) in lines {
+            *merged.entry(energy).or_default() += activity * y;
+        }
+    }
+
+    let mut lines: Vec<(Energy, f64)> = merged
+        .into_iter()
+        .filter(|&(_, y)| y >= min_yield)
+        .collect();
+    lines.sort_by(|a, b| b.1.total_cmp(&a.1));
+    lines
+}
+
+// CSV rows of photon lines sorted by energy, each starting with prefix
+fn write_csv_rows(
+    writer: &mut impl Write,
+    prefix: &str,
+    spectrum: &[RadSpectrum],
+) -> Result<(), Error> {
+    let photons: Vec<&RadSpectrum> = spectrum.iter().filter(|r| r.r#type.is_photon()).collect();
+    for r in sort_by_energy(&photons, |r| r.energy, SortOrder::Ascending) {
+        writeln!(
+            writer,
+            "{}{:.3},{},{}",
+            prefix,
+            r.energy * 1e3,
+            r.r#yield.as_per_decay(),
+            r.r#type
+        )?;
+    }
+    Ok(())
+}
+
+// Air-kerma rate constant (µGy·m²/(MBq·h)) of photon lines (energy (MeV), yield)
+fn air_kerma_rate_constant(lines: &[(f64, f64)]) -> Result<f64, Error> {
+    let mut kerma = 0.;
+    for &(energy, r#yield) in lines {
+        if energy >= AIR_KERMA_CUTOFF_MEV {
+            kerma += r#yield * energy * air_mu_en(energy)?;
+        }
+    }
+
+    // cm2/g to m2/kg, Gy to µGy, Bq to MBq and s to h
+    Ok(kerma * MEV_TO_J * 0.1 / (4. * std::f64::consts::PI) * 1e6 * 1e6 * 3600.)
+}
+
+// Mass energy-absorption coefficient (cm2/g) of air at energy (MeV)
+fn air_mu_en(energy: f64) -> Result<f64, Error> {
+    let i = AIR_MU_EN.partition_point(|&(e, _)| e < energy);
+    match (i.checked_sub(1).map(|i| AIR_MU_EN[i]), AIR_MU_EN.get(i)) {
+        (_, Some(&(e1, mu1))) if e1 == energy => Ok(mu1),
+        (Some((e0, mu0)), Some(&(e1, mu1))) => {
+            let f = (energy / e0).ln() / (e1 / e0).ln();
+            Ok((mu0.ln() + f * (mu1 / mu0).ln()).exp())
+        }
+        _ => Err(Error::InvalidEnergy((energy * 1e6).round() as Energy)),
+    }
+}
+
+// Mean energy of piecewise linear spectrum of grid points (energy, density)
+fn beta_mean_energy(points: &[(f64, f64)]) -> f64 {
+    let (number, energy) = beta_moments(points);
+
+    if number > 0. {
+        energy / number
+    } else {
+        0.
+    }
+}
+
+// Number and total energy of piecewise linear spectrum of grid points (energy, density)
+pub(super) fn beta_moments(points: &[(f64, f64)]) -> (f64, f64) {
+    points.windows(2).fold((0., 0.), |(n, e), w| {
+        let ((x0, y0), (x1, y1)) = (w[0], w[1]);
+        let h = x1 - x0;
+        (
+            n + h * (y0 + y1) / 2.,
+            e + h * (2. * x0 * y0 + x0 * y1 + x1 * y0 + 2. * x1 * y1) / 6.,
+        )
+    })
+}
+
+// Mean energy (MeV) of neutron bins, each taken at its center
+fn neutron_mean_energy(spectrum: &[NsfSpectrum]) -> f64 {
+    let (number, energy) = spectrum.iter().fold((0., 0.), |(n, e), b| {
+        let center = (b.energy_lower + b.energy_upper) / 2.;
+        let y = b.r#yield.as_per_decay();
+        (n + y, e + y * center)
+    });
+
+    if number > 0. {
+        energy / number
+    } else {
+        0.
+    }
+}
+
+// Total (yield, energy) of lines of each radiation type present
+fn emission_summary(spectrum: &[RadSpectrum]) -> HashMap<RadiationType, (f64, f64)> {
+    spectrum
+        .iter()
+        .map(|r| r.r#type)
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .map(|t| (t, (sum_yield(spectrum, t), sum_energy(spectrum, t))))
+        .collect()
+}
+
+// Sum of yield times energy (MeV) of lines
+fn energy_per_decay<'a>(spectrum: impl Iterator<Item = &'a RadSpectrum>) -> f64 {
+    spectrum.map(|r| r.r#yield.as_per_decay() * r.energy).sum()
+}
+
+// Dose rate in air (Gy/s per Bq/m3) of semi-infinite cloud, for energy (MeV) per decay
+fn semi_infinite_cloud_dose_rate(energy: f64) -> f64 {
+    0.5 * energy * MEV_TO_J / AIR_DENSITY
+}
+
+#[cfg(feature = "ndarray")]
+impl Icrp107 {
+    /// RAD lines of nuclide as an array of (energy (MeV), yield (/nt)) rows
+    pub fn rad_as_array(&self, nuclide: Nuclide) -> Result<ndarray::Array2<f64>, Error> {
+        spectrum_array(self.rad_of(nuclide)?)
+    }
+}
+
+#[cfg(feature = "ndarray")]
+fn spectrum_array(spectrum: &[RadSpectrum]) -> Result<ndarray::Array2<f64>, Error> {
+    let values: Vec<f64> = spectrum
+        .iter()
+        .flat_map(|r| [r.energy, r.r#yield.as_per_decay()])
+        .collect();
+    ndarray::Array2::from_shape_vec((spectrum.len(), 2), values)
+        .map_err(|e| Error::Unexpected(e.into()))
+}
+
+// Air kerma rate (Gy/s) at 1 m above a plane source of photon lines
+fn ground_plane_dose_rate(lines: &[(f64, f64)], surface: f64) -> f64 {
+    GROUND_PLANE_FACTOR * photon_energy_per_decay(lines) * surface
+}
+
+// Lines escaping a slab of material with thickness (cm)
+fn self_absorbed(
+    lines: &[(f64, f64)],
+    thickness: f64,
+    material: &Material,
+    attenuation: &dyn AttenuationData,
+) -> Result<Vec<(f64, f64)>, Error> {
+    lines
+        .iter()
+        .map(|&(e, y)| {
+            let mu_over_rho = attenuation
+                .mass_attenuation(material, e)
+                .ok_or(Error::InvalidEnergy((e * 1e6).round() as Energy))?;
+            let mu_x = mu_over_rho * material.density() * thickness;
+            let factor = if mu_x > 0. {
+                -(-mu_x).exp_m1() / mu_x
+            } else {
+                1.
+            };
+            Ok((e, y * factor))
+        })
+        .collect()
+}
+
+// Currie's minimum detectable activity (Bq) for counts per decay
+fn currie_mda(background_cps: f64, count_time: f64, counts_per_decay: f64) -> f64 {
+    let detection_limit = 2.71 + 4.65 * (background_cps * count_time).sqrt();
+    detection_limit / (counts_per_decay * count_time)
+}
+
+fn peak_lines(spectrum: &[RadSpectrum]) -> HashMap<RadiationType, (f64, f64)> {
+    let mut peaks: HashMap<RadiationType, (f64, f64)> = HashMap::new();
+    for r in spectrum {
+        let line = (r.energy, r.r#yield.as_per_decay());
+        let peak = peaks.entry(r.r#type).or_insert(line);
+        if line.1 > peak.1 {
+            *peak = line;
+        }
+    }
+    peaks
+}
+
+// Photon energy (MeV) per decay
+fn photon_energy_per_decay(lines: &[(f64, f64)]) -> f64 {
+    lines.iter().map(|(e, y)| e * y).sum()
+}
+
+fn highest_energy_above(lines: &[(f64, f64)], threshold: f64) -> Option<f64> {
+    lines
+        .iter()
+        .map(|(e, _)| *e)
+        .filter(|e| *e > threshold)
+        .reduce(f64::max)
+}
+
+fn normalize(lines: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    let tot: f64 = lines.iter().map(|(_, y)| y).sum();
+    lines.iter().map(|&(e, y)| (e, y / tot)).collect()
+}
+
+fn detectable_fraction(lines: &[(f64, f64)], threshold: f64) -> f64 {
+    lines
+        .iter()
+        .filter(|(e, _)| *e > threshold)
+        .map(|(_, y)| y)
+        .sum::<f64>()
+        .min(1.)
+}
+
+// Maximum number of entries per card line, which keeps lines within 80 columns
+const MCNP_ENTRIES_PER_LINE: usize = 5;
+
+fn mcnp_source(nuclide: Nuclide, lines: &[(f64, f64)]) -> String {
+    let card = |name: &str, values: Vec<f64>| {
+        values
+            .chunks(MCNP_ENTRIES_PER_LINE)
+            .enumerate()
+            .map(|(i, chunk)| {
+                let values: Vec<String> = chunk.iter().map(|v| format!("{:.5E}", v)).collect();
+                if i == 0 {
+                    format!("{} {}", name, values.join(" "))
+                } else {
+                    format!("      {}", values.join(" "))
+                }
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    };
+
+    format!(
+        "c {} photon source\nSDEF PAR=2 ERG=D1\n{}\n{}\n",
+        nuclide,
+        card("SI1 L", lines.iter().map(|(e, _)| *e).collect()),
+        card("SP1  ", normalize(lines).iter().map(|(_, p)| *p).collect()),
+    )
+}
+
+// Overlap of normalized intensities between measured peaks and library lines
+fn overlap_score(peaks: &[(f64, f64)], lines: &[(f64, f64)], tol: f64) -> f64 {
+    let peak_tot: f64 = peaks.iter().map(|(_, i)| i).sum();
+    let line_tot: f64 = lines.iter().map(|(_, y)| y).sum();
+
+    if peak_tot <= 0. || line_tot <= 0. {
+        return 0.;
+    }
+
+    let mut matched = vec![0f64; peaks.len()];
+    for &(energy, y) in lines {
+        let nearest = peaks
+            .iter()
+            .enumerate()
+            .map(|(i, (e, _))| (i, (e - energy).abs()))
+            .filter(|(_, de)| *de <= tol)
+            .min_by(|(_, a), (_, b)| a.total_cmp(b));
+
+        if let Some((i, _)) = nearest {
+            matched[i] += y / line_tot;
+        }
+    }
+
+    peaks
+        .iter()
+        .zip(matched)
+        .map(|((_, i), q)| (i / peak_tot).min(q))
+        .sum()
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::spectrum::Yield;
+    use super::*;
+    use crate::primitive::attr::AtomicMass;
+    use crate::primitive::notation::MaterialBuilder;
+    use crate::primitive::Symbol;
+    use std::sync::Arc;
+
+    const DATA_PATH: &str = "data/icrp107";
+
+    #[test]
+    fn score_of_peaks() {
+        let lines = vec![(0.1, 0.3), (0.5, 0.6), (1.0, 0.1)];
+
+        let exact = overlap_score(&[(0.1, 3.), (0.5, 6.), (1.0, 1.)], &lines, 0.001);
+        assert!((exact - 1.).abs() < 1e-12);
+
+        let partial = overlap_score(&[(0.5001, 1.)], &lines, 0.001);
+        assert!((partial - 0.6).abs() < 1e-12);
+
+        let unmatched = overlap_score(&[(0.2, 1.)], &lines, 0.001);
+        assert_eq!(unmatched, 0.);
+    }
+
+    #[test]
+    fn mcnp_source_cards() {
+        let lines: Vec<(f64, f64)> = (1..=7).map(|i| (0.1 * i as f64, 0.2)).collect();
+        let source = mcnp_source("Co-60".parse().unwrap(), &lines);
+
+        assert!(source.contains("SDEF PAR=2 ERG=D1\n"));
+        assert!(source.contains("SI1 L 1.00000E-1 2.00000E-1"));
+        assert!(source.lines().all(|l| l.len() <= 80));
+
+        let sp: Vec<f64> = source
+            .lines()
+            .skip_while(|l| !l.starts_with("SP1"))
+            .flat_map(|l| l.trim_start_matches("SP1").split_whitespace())
+            .map(|v| v.parse().unwrap())
+            .collect();
+        assert_eq!(sp.len(), 7);
+        assert!((sp.iter().sum::<f64>() - 1.).abs() < 1e-4);
+    }
+
+    #[test]
+    fn gamma_power_co60() {
+        let lines = vec![(1.173228, 0.9985), (1.332492, 0.999826)];
+        let energy = 1.173228 * 0.9985 + 1.332492 * 0.999826;
+        assert!((photon_energy_per_decay(&lines) - energy).abs() < 1e-12);
+
+        // 1 Ci of Co-60 emits about 15 mW as gamma rays
+        let power = 3.7e10 * photon_energy_per_decay(&lines) * MEV_TO_J;
+        assert!((power - 3.7e10 * energy * 1.602176634e-13).abs() < 1e-15);
+        assert!((power - 0.0148).abs() < 1e-4);
+    }
+
+    #[test]
+    fn air_mu_en_interpolation() {
+        assert_eq!(air_mu_en(1.).unwrap(), 0.02789);
+        assert_eq!(air_mu_en(0.01).unwrap(), 4.742);
+        assert_eq!(air_mu_en(20.).unwrap(), 0.01311);
+
+        // log-log midpoint is the geometric mean
+        let mid = air_mu_en((1.25f64 * 1.5).sqrt()).unwrap();
+        assert!((mid - (0.02666f64 * 0.02547).sqrt()).abs() < 1e-12);
+
+        assert!(matches!(air_mu_en(0.005), Err(Error::InvalidEnergy(5000))));
+        assert!(air_mu_en(25.).is_err());
+    }
+
+    #[test]
+    fn air_kerma_rate_constants() {
+        // published values are 0.305-0.309 µGy·m²/(MBq·h) for Co-60
+        let co60 = air_kerma_rate_constant(&[(1.173228, 0.9985), (1.332492, 0.999826)]).unwrap();
+        assert!((co60 / 0.307 - 1.).abs() < 0.02);
+
+        // Cs-137 with Ba-137m in equilibrium and Ba K X-rays, published as 0.077-0.078
+        let cs137 = air_kerma_rate_constant(&[
+            (0.661657, 0.851),
+            (0.031817, 0.0199),
+            (0.032194, 0.0364),
+            (0.0364, 0.0105),
+            (0.004470, 0.0091),
+        ])
+        .unwrap();
+        assert!((cs137 / 0.0775 - 1.).abs() < 0.03);
+
+        assert_eq!(air_kerma_rate_constant(&[]).unwrap(), 0.);
+    }
+
+    #[test]
+    fn beta_mean_energy_of_grid() {
+        // density proportional to energy on a non-uniform grid, mean is 2/3 of endpoint
+        let points = [(0., 0.), (0.1, 0.2), (0.5, 1.), (2., 4.)];
+        assert!((beta_mean_energy(&points) - 4. / 3.).abs() < 1e-12);
+
+        // uniform density has its mean at the center
+        let points = [(0., 1.), (0.3, 1.), (1., 1.)];
+        assert!((beta_mean_energy(&points) - 0.5).abs() < 1e-12);
+
+        assert_eq!(beta_mean_energy(&[]), 0.);
+        assert_eq!(beta_mean_energy(&[(0.5, 0.)]), 0.);
+    }
+
+    #[test]
+    #[ignore]
+    fn beta_energies_sr90_y90_from_data() {
+        let data = Icrp107::open(DATA_PATH).unwrap();
+        let sr90: Nuclide = "Sr-90".parse().unwrap();
+        let y90: Nuclide = "Y-90".parse().unwrap();
+
+        assert!((data.beta_mean_energy(sr90).unwrap() / 0.1958 - 1.).abs() < 0.01);
+        assert!((data.beta_endpoint_energy(sr90).unwrap() / 0.546 - 1.).abs() < 0.01);
+        assert!((data.beta_mean_energy(y90).unwrap() / 0.9267 - 1.).abs() < 0.01);
+        assert!((data.beta_endpoint_energy(y90).unwrap() / 2.2801 - 1.).abs() < 0.01);
+
+        let am241: Nuclide = "Am-241".parse().unwrap();
+        assert_eq!(data.beta_mean_energy(am241).unwrap(), 0.);
+        assert_eq!(data.beta_endpoint_energy(am241).unwrap(), 0.);
+    }
+
+    #[test]
+    fn neutron_mean_energy_cf252() {
+        // Watt spectrum of Cf-252 (a = 1.025 MeV, b = 2.926 /MeV) in 0.05 MeV bins, with
+        // 3.77 neutrons per fission in 3.09% of decays
+        let watt = |e: f64| (-e / 1.025).exp() * (2.926 * e).sqrt().sinh();
+        let bins: Vec<(f64, f64)> = (0..400)
+            .map(|i| (0.05 * i as f64, watt(0.05 * (i as f64 + 0.5))))
+            .collect();
+        let total: f64 = bins.iter().map(|(_, w)| w).sum();
+        let spectrum: Vec<NsfSpectrum> = bins
+            .iter()
+            .map(|(e, w)| NsfSpectrum {
+                energy_lower: *e,
+                energy_upper: e + 0.05,
+                r#yield: Yield::from_per_decay(0.0309 * 3.77 * w / total).unwrap(),
+            })
+            .collect();
+
+        let neutrons: f64 = spectrum.iter().map(|n| n.r#yield.as_per_decay()).sum();
+        assert!((neutrons - 0.1165).abs() < 1e-3);
+        // mean of Watt spectrum is 3a/2 + a²b/4
+        let mean = 1.5 * 1.025 + 1.025f64.powi(2) * 2.926 / 4.;
+        assert!((neutron_mean_energy(&spectrum) / mean - 1.).abs() < 0.01);
+
+        assert_eq!(neutron_mean_energy(&[]), 0.);
+    }
+
+    #[test]
+    #[ignore]
+    fn neutron_cf252_from_data() {
+        let data = Icrp107::open(DATA_PATH).unwrap();
+        let cf252: Nuclide = "Cf-252".parse().unwrap();
+
+        assert!((data.neutron_yield(cf252).unwrap() / 0.1165 - 1.).abs() < 0.02);
+        let mean = data.neutron_mean_energy(cf252).unwrap();
+        assert!(mean > 2. && mean < 2.4);
+
+        let cs137: Nuclide = "Cs-137".parse().unwrap();
+        assert_eq!(data.neutron_yield(cs137).unwrap(), 0.);
+        assert_eq!(data.neutron_mean_energy(cs137).unwrap(), 0.);
+    }
+
+    #[test]
+    #[ignore]
+    fn gamma_power_co60_from_data() {
+        let data = Icrp107::open(DATA_PATH).unwrap();
+        let power = data.gamma_power("Co-60".parse().unwrap(), 3.7e10).unwrap();
+        assert!((power - 0.0148).abs() < 1e-4);
+    }
+
+    #[test]
+    fn beta_immersion_kr85() {
+        // mean beta energy of Kr-85 (MeV) times yield
+        let rate = semi_infinite_cloud_dose_rate(0.2505 * 0.99563);
+        assert!((rate - 0.5 * 0.2505 * 0.99563 * 1.602176634e-13 / 1.205).abs() < 1e-27);
+        assert!((rate / 1.66e-14 - 1.).abs() < 0.01);
+    }
+
+    #[test]
+    #[ignore]
+    fn beta_immersion_kr85_from_data() {
+        let data = Icrp107::open(DATA_PATH).unwrap();
+        let rate = data
+            .beta_immersion_coefficient("Kr-85".parse().unwrap())
+            .unwrap();
+        assert!((rate / 1.66e-14 - 1.).abs() < 0.02);
+    }
+
+    #[test]
+    fn ground_plane_cs137() {
+        // gamma of Ba-137m in equilibrium with Cs-137
+        let lines = vec![(0.661657, 0.944 * 0.899)];
+        let rate = ground_plane_dose_rate(&lines, 1e3);
+        assert!((rate - 9.5e-16 * 0.661657 * 0.944 * 0.899 * 1e3).abs() < 1e-24);
+
+        // about 2 nGy/h per kBq/m2
+        let ngy_per_h = rate * 3600. * 1e9;
+        assert!(ngy_per_h > 1.5 && ngy_per_h < 2.5);
+    }
+
+    #[test]
+    #[ignore]
+    fn ground_plane_cs137_from_data() {
+        let data = Icrp107::open(DATA_PATH).unwrap();
+        let cs137 = data
+            .ground_contamination_dose_rate("Cs-137".parse().unwrap(), 1e3)
+            .unwrap();
+        let ba137m = data
+            .ground_contamination_dose_rate("Ba-137m".parse().unwrap(), 1e3)
+            .unwrap();
+
+        let ngy_per_h = (cs137 + 0.944 * ba137m) * 3600. * 1e9;
+        assert!(ngy_per_h > 1.5 && ngy_per_h < 2.5);
+    }
+
+    // Attenuation of water below and above 100 keV
+    struct StepAttenuation;
+
+    impl AttenuationData for StepAttenuation {
+        fn mass_attenuation(&self, _material: &Material, energy_mev: f64) -> Option<f64> {
+            if energy_mev > 2. {
+                None
+            } else if energy_mev < 0.1 {
+                Some(0.36)
+            } else {
+                Some(0.086)
+            }
+        }
+    }
+
+    struct Water;
+
+    impl AtomicMass for Water {
+        fn atomic_mass(&self, symbol: Symbol) -> Result<f64, Error> {
+            match symbol {
+                Symbol::H => Ok(1.008),
+                Symbol::O => Ok(15.999),
+                _ => Err(Error::InvalidSymbol(symbol.to_string())),
+            }
+        }
+    }
+
+    #[test]
+    fn self_absorption_of_lines() {
+        let water = MaterialBuilder::new(Arc::new(Water))
+            .formula("H2O")
+            .unwrap()
+            .weight(1.)
+            .density(1.)
+            .build()
+            .unwrap();
+        let lines = vec![(0.032194, 0.0364), (0.661657, 0.851)];
+
+        let absorbed = self_absorbed(&lines, 5., &water, &StepAttenuation).unwrap();
+        let low = absorbed[0].1 / lines[0].1;
+        let high = absorbed[1].1 / lines[1].1;
+        assert!(low < high);
+        assert!((low - (1. - (-1.8f64).exp()) / 1.8).abs() < 1e-12);
+        assert!(high < 1.);
+
+        let thin = self_absorbed(&lines, 0., &water, &StepAttenuation).unwrap();
+        assert_eq!(thin, lines);
+
+        assert!(matches!(
+            self_absorbed(&[(2.01, 1.)], 5., &water, &StepAttenuation),
+            Err(Error::InvalidEnergy(2_010_000))
+        ));
+    }
+
+    #[test]
+    fn currie_mda_by_hand() {
+        // B = 1000 counts, L_D = 2.71 + 4.65 * 31.6228 = 149.756 counts
+        let mda = currie_mda(1., 1000., 0.1 * 0.5);
+        assert!((mda - 149.756 / 50.).abs() < 1e-4);
+
+        assert!((currie_mda(0., 100., 1.) - 0.0271).abs() < 1e-12);
+    }
+
+    #[test]
+    fn mda_arguments() {
+        let data = Icrp107::open(std::env::temp_dir()).unwrap();
+        let cs137: Nuclide = "Cs-137".parse().unwrap();
+        let mda = |background_cps, count_time_s, efficiency| {
+            data.minimum_detectable_activity(cs137, 0.662, background_cps, count_time_s, efficiency)
+        };
+
+        assert!(matches!(
+            mda(-1., 1000., 0.1),
+            Err(Error::InvalidCountRate(_))
+        ));
+        assert!(matches!(
+            mda(f64::NAN, 1000., 0.1),
+            Err(Error::InvalidCountRate(_))
+        ));
+        assert!(matches!(mda(1., 0., 0.1), Err(Error::InvalidTime(_))));
+        assert!(matches!(
+            mda(1., f64::INFINITY, 0.1),
+            Err(Error::InvalidTime(_))
+        ));
+        assert!(matches!(
+            mda(1., 1000., 0.),
+            Err(Error::InvalidEfficiency(_))
+        ));
+        assert!(matches!(
+            mda(1., 1000., 1.5),
+            Err(Error::InvalidEfficiency(_))
+        ));
+    }
+
+    #[test]
+    #[ignore]
+    fn mda_cs137() {
+        let data = Icrp107::open(DATA_PATH).unwrap();
+        let cs137: Nuclide = "Cs-137".parse().unwrap();
+
+        let mda = data
+            .minimum_detectable_activity(cs137, 0.661657, 1., 1000., 0.1)
+            .unwrap();
+        assert!(mda > 0.);
+        assert!(matches!(
+            data.minimum_detectable_activity(cs137, 1.0, 1., 1000., 0.1),
+            Err(Error::NoPhotonLine(n, 1_000_000)) if n == cs137
+        ));
+    }
+
+    #[test]
+    fn gamma_lines_of_ba133() {
+        let spectrum: Vec<RadSpectrum> = [
+            " 5  3.6200E-01  3.0973E-02X  ",
+            " 1  2.1400E-02  5.3162E-02G  ",
+            " 1  3.2900E-01  8.0998E-02G  ",
+            " 1  7.1600E-02  2.7640E-01G  ",
+            " 1  1.8340E-01  3.0285E-01G  ",
+            " 1  6.2050E-01  3.5601E-01G  ",
+            " 1  8.9400E-02  3.8385E-01G  ",
+            " 3  4.5000E-01  4.5000E-02AE ",
+        ]
+        .iter()
+        .map(|l| l.parse().unwrap())
+        .collect();
+
+        let lines = gamma_lines(&spectrum, 0.05, false);
+        assert_eq!(
+            lines,
+            vec![
+                (356_010, 0.6205),
+                (80_998, 0.329),
+                (302_850, 0.1834),
+                (383_850, 0.0894),
+                (276_400, 0.0716),
+            ]
+        );
+
+        let with_x = gamma_lines(&spectrum, 0.05, true);
+        assert_eq!(with_x.len(), 6);
+        assert_eq!(with_x[1], (30_973, 0.362));
+
+        assert_eq!(
+            gamma_lines(&spectrum, 0.6205, false),
+            vec![(356_010, 0.6205)]
+        );
+        assert_eq!(gamma_lines(&spectrum, 0., false).len(), 6);
+        assert!(gamma_lines(&spectrum, 0.7, true).is_empty());
+        assert!(gamma_lines(&spectrum[7..], 0., true).is_empty());
+    }
+
+    #[test]
+    fn peak_lines_of_types() {
+        let spectrum: Vec<RadSpectrum> = [
+            " 1  8.5100E-01  6.6166E-01G  ",
+            " 5  3.6400E-02  3.2194E-02X  ",
+            " 5  1.9900E-02  3.1817E-02X  ",
+            " 5  7.0000E-03  3.6400E-02X  ",
+        ]
+        .iter()
+        .map(|l| l.parse().unwrap())
+        .collect();
+
+        let peaks = peak_lines(&spectrum);
+        assert_eq!(peaks.len(), 2);
+        assert_eq!(peaks[&RadiationType::Gamma], (0.66166, 0.851));
+        assert_eq!(peaks[&RadiationType::X], (0.032194, 0.0364));
+    }
+
+    #[test]
+    #[cfg(feature = "ndarray")]
+    fn spectrum_as_array() {
+        let spectrum: Vec<RadSpectrum> = [
+            " 1  8.5100E-01  6.6166E-01G  ",
+            " 5  3.6400E-02  3.2194E-02X  ",
+        ]
+        .iter()
+        .map(|l| l.parse().unwrap())
+        .collect();
+
+        let array = spectrum_array(&spectrum).unwrap();
+        assert_eq!(array.shape(), &[2, 2]);
+        assert_eq!(array[[0, 0]], 0.66166);
+        assert_eq!(array[[0, 1]], 0.851);
+        assert_eq!(array[[1, 0]], 0.032194);
+        assert_eq!(array[[1, 1]], 0.0364);
+
+        assert_eq!(spectrum_array(&[]).unwrap().shape(), &[0, 2]);
+    }
+
+    #[test]
+    fn alpha_above_threshold() {
+        let lines = vec![(4.151, 0.21), (4.198, 0.79)];
+        assert_eq!(highest_energy_above(&lines, 4.), Some(4.198));
+        assert_eq!(highest_energy_above(&lines, 4.17), Some(4.198));
+        assert_eq!(highest_energy_above(&lines, 5.), None);
+    }
+
+    #[test]
+    #[ignore]
+    fn alpha_emitters_above_8mev() {
+        let data = Icrp107::open(DATA_PATH).unwrap();
+        let nuclides = data.nuclides_with_alpha_above(8.).unwrap();
+
+        assert!(nuclides
+            .iter()
+            .any(|(n, _)| n == &"Po-212".parse().unwrap()));
+        assert!(!nuclides.iter().any(|(n, _)| n == &"U-238".parse().unwrap()));
+        assert!(nuclides.iter().all(|(_, e)| *e > 8.));
+    }
+
+    #[test]
+    fn normalized_photon_lines() {
+        let pdf = normalize(&[(0.032, 0.0364), (0.662, 0.851), (0.036, 0.0086)]);
+        assert_eq!(pdf.len(), 3);
+        assert_eq!(pdf[1].0, 0.662);
+        assert!((pdf.iter().map(|(_, p)| p).sum::<f64>() - 1.).abs() < 1e-12);
+    }
+
+    #[test]
+    #[ignore]
+    fn photon_pdf_co60() {
+        let data = Icrp107::open(DATA_PATH).unwrap();
+        let pdf = data.photon_pdf("Co-60".parse().unwrap()).unwrap();
+        assert!((pdf.iter().map(|(_, p)| p).sum::<f64>() - 1.).abs() < 1e-12);
+    }
+
+    #[test]
+    fn photon_pdf_without_photon() {
+        let path = std::env::temp_dir().join("radioactive-icrp107-photon-pdf");
+        std::fs::create_dir_all(&path).unwrap();
+        let ndx = "H-3       12.312y B-                                 He-3        0 1.0000E+00             0        0.0             0        0.0             0        0.0  0.0000 0.00568 0.00000   0   0   1    1   0   3.016049 0.000E+000.000E+00\n";
+        std::fs::write(path.join("ICRP-07.NDX"), format!("header\n{}", ndx)).unwrap();
+        std::fs::write(path.join("ICRP-07.RAD"), "").unwrap();
+
+        let data = Icrp107::open(&path).unwrap();
+        let h3: Nuclide = "H-3".parse().unwrap();
+        assert!(matches!(
+            data.photon_pdf(h3),
+            Err(Error::NoPhotonEmission(n)) if n == h3
+        ));
+    }
+
+    #[test]
+    fn fraction_above_threshold() {
+        let lines = vec![(0.03, 0.07), (0.662, 0.85)];
+        assert!((detectable_fraction(&lines, 0.1) - 0.85).abs() < 1e-12);
+        assert!((detectable_fraction(&lines, 0.01) - 0.92).abs() < 1e-12);
+        assert_eq!(detectable_fraction(&lines, 1.), 0.);
+
+        let cascade = vec![(1.173, 0.9985), (1.332, 0.9998)];
+        assert_eq!(detectable_fraction(&cascade, 1.), 1.);
+    }
+
+    #[test]
+    fn dominant_line_ties() {
+        let spectrum: Vec<RadSpectrum> = [
+            " 2  2.0000E-01  1.0000E-01B- ",
+            " 1  5.0000E-01  1.1732E+00G  ",
+            " 1  5.0000E-01  1.3325E+00G  ",
+            " 6  5.0000E-01  5.0000E-02X  ",
+        ]
+        .iter()
+        .map(|l| l.parse().unwrap())
+        .collect();
+
+        assert_eq!(
+            dominant_line(spectrum.iter()),
+            Some((RadiationType::Gamma, 1_332_500, 0.5))
+        );
+        assert_eq!(
+            dominant_line(
+                spectrum
+                    .iter()
+                    .filter(|r| r.r#type == RadiationType::BetaMinus)
+            ),
+            Some((RadiationType::BetaMinus, 100_000, 0.2))
+        );
+        assert_eq!(dominant_line([].iter()), None);
+    }
+
+    #[test]
+    fn merge_weighted_lines() {
+        let members = vec![
+            (1., vec![(100_000, 0.5), (200_000, 0.1)]),
+            (0.5, vec![(200_000, 0.4), (300_000, 0.02)]),
+        ];
+        let lines = merge_lines(&members, 0.);
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], (100_000, 0.5));
+        assert_eq!(lines[1].0, 200_000);
+        assert!((lines[1].1 - 0.3).abs() < 1e-12);
+        assert_eq!(lines[2], (300_000, 0.01));
+
+        assert_eq!(merge_lines(&members, 0.2).len(), 2);
+        assert!(merge_lines(&[], 0.).is_empty());
+    }
+
+    #[test]
+    #[ignore]
+    fn identify_cs137() {
+        let data = Icrp107::open(DATA_PATH).unwrap();
+        let cs137: Nuclide = "Cs-137".parse().unwrap();
+
+        let peaks = data.photon_lines(cs137).unwrap();
+        let candidates = data.identify(&peaks, 1e-4).unwrap();
+
+        assert_eq!(candidates[0].0, cs137);
+        assert!((candidates[0].1 - 1.).abs() < 1e-9);
+    }
+}
@@ -1,78 +1,560 @@
+mod abundance;
+mod card;
+mod emission;
 mod ndx;
 mod reader;
 pub mod spectrum;
+mod validate;
 
 use once_cell::sync::OnceCell;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
+use crate::decaychain::DecaySeries;
 use crate::error::Error;
-use crate::primitive::attr::{NuclideDecayMode, NuclideHalfLife, NuclideProgeny};
-use crate::primitive::{DecayModeSet, HalfLife, Nuclide, Progeny};
+use crate::primitive::attr::{
+    NuclideDecayMode, NuclideHalfLife, NuclideMass, NuclideProgeny, SpecificActivity,
+};
+use crate::primitive::notation::atomic_number;
+use crate::primitive::{DecayModeSet, HalfLife, Nuclide, Progeny, TimeUnit, YearConvention};
 use reader::{IndexReader, SpectrumReader};
-use spectrum::{ack, bet, nsf, rad};
+use spectrum::{ack, bet, nsf, rad, sort_by_energy, SortOrder};
+
+pub use validate::{NdxQuantity, ValidationWarning};
+
+/// Joules per MeV
+const MEV_TO_J: f64 = 1.602_176_634e-13;
+
+/// Fractions of decay energy carried by each class of radiation, which sum to 1
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EnergyPartition {
+    pub photon: f64,
+    /// Beta particles and mono-energetic electrons
+    pub electron: f64,
+    pub alpha: f64,
+    /// Remainder not emitted as other radiation
+    pub neutrino: f64,
+}
+
+impl EnergyPartition {
+    // Partition of q (MeV) with emitted energies (MeV) of alpha, electron and photon
+    fn new(q: f64, alpha: f64, electron: f64, photon: f64) -> Result<Self, Error> {
+        let emitted = alpha + electron + photon;
+        if !(q.is_finite() && q > 0.) || emitted > q * (1. + 1e-6) {
+            return Err(Error::InvalidDecayEnergy(format!(
+                "{} MeV with emitted energy {} MeV",
+                q, emitted
+            )));
+        }
+
+        Ok(Self {
+            photon: photon / q,
+            electron: electron / q,
+            alpha: alpha / q,
+            neutrino: (1. - emitted / q).max(0.),
+        })
+    }
+}
 
 #[derive(Debug)]
 pub struct Icrp107 {
     path: PathBuf,
+    only: Option<HashSet<Nuclide>>,
+    year_convention: YearConvention,
     ndx: OnceCell<HashMap<Nuclide, ndx::Attribute>>,
     rad: OnceCell<HashMap<Nuclide, Vec<rad::RadSpectrum>>>,
     bet: OnceCell<HashMap<Nuclide, Vec<bet::BetSpectrum>>>,
     ack: OnceCell<HashMap<Nuclide, Vec<ack::AckSpectrum>>>,
     nsf: OnceCell<HashMap<Nuclide, Vec<nsf::NsfSpectrum>>>,
+    parents: OnceCell<HashMap<Nuclide, Vec<Nuclide>>>,
+    lambdas: OnceCell<HashMap<Nuclide, f64>>,
+}
+
+#[derive(Debug, Default)]
+pub struct Icrp107Builder {
+    path: Option<PathBuf>,
+    strict: bool,
+    only: Option<HashSet<Nuclide>>,
+    year_convention: YearConvention,
+}
+
+impl Icrp107Builder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Directory containing ICRP-07.* files, each of which may be gzip-compressed as
+    /// ICRP-07.*.gz instead
+    pub fn path<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.path = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Load the index on build, and fail if any nuclide given by `only` is absent
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Keep only the given nuclides and their progeny in the dataset
+    pub fn only(mut self, nuclides: &[Nuclide]) -> Self {
+        self.only = Some(nuclides.iter().copied().collect());
+        self
+    }
+
+    /// Length of year used for half-lives given in years
+    ///
+    /// It applies to decay constants and times derived from half-lives, while half-lives
+    /// themselves are kept in years as given by the index.
+    pub fn year_convention(mut self, year_convention: YearConvention) -> Self {
+        self.year_convention = year_convention;
+        self
+    }
+
+    pub fn build(self) -> Result<Icrp107, Error> {
+        let path = self.path.ok_or(Error::InvalidFilePath)?;
+        if !path.is_dir() {
+            return Err(Error::InvalidFilePath);
+        }
+
+        let data = Icrp107 {
+            path,
+            only: self.only,
+            year_convention: self.year_convention,
+            ndx: OnceCell::new(),
+            rad: OnceCell::new(),
+            bet: OnceCell::new(),
+            ack: OnceCell::new(),
+            nsf: OnceCell::new(),
+            parents: OnceCell::new(),
+            lambdas: OnceCell::new(),
+        };
+
+        if self.strict {
+            let ndx = data.ndx()?;
+            if let Some(missing) = data.only.iter().flatten().find(|n| !ndx.contains_key(n)) {
+                return Err(Error::InvalidNuclide(missing.to_string()));
+            }
+        }
+
+        Ok(data)
+    }
 }
 
 impl Icrp107 {
     pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
-        let path_buf = path.as_ref().to_path_buf();
-
-        if path_buf.is_dir() {
-            Ok(Self {
-                path: path_buf,
-                ndx: OnceCell::new(),
-                rad: OnceCell::new(),
-                bet: OnceCell::new(),
-                ack: OnceCell::new(),
-                nsf: OnceCell::new(),
-            })
-        } else {
-            Err(Error::InvalidFilePath)
+        Icrp107Builder::new().path(path).build()
+    }
+
+    pub fn builder() -> Icrp107Builder {
+        Icrp107Builder::new()
+    }
+
+    // Drop nuclides not in the index, i.e. neither selected by the builder nor their progeny
+    fn retain<V>(&self, mut map: HashMap<Nuclide, V>) -> Result<HashMap<Nuclide, V>, Error> {
+        if self.only.is_some() {
+            let ndx = self.ndx()?;
+            map.retain(|nuclide, _| ndx.contains_key(nuclide));
         }
+        Ok(map)
     }
 
     pub fn ndx(&self) -> Result<&HashMap<Nuclide, ndx::Attribute>, Error> {
-        self.ndx
-            .get_or_try_init(|| IndexReader::new(&self.path.join("ICRP-07.NDX"))?.read())
+        self.ndx.get_or_try_init(|| {
+            let mut ndx = IndexReader::new(&self.path.join("ICRP-07.NDX"))?.read()?;
+            if let Some(only) = &self.only {
+                // keep the whole chains, so that progeny are not taken for stable nuclides
+                let mut kept: HashSet<Nuclide> = HashSet::new();
+                let mut queue: Vec<Nuclide> = only.iter().copied().collect();
+                while let Some(nuclide) = queue.pop() {
+                    if let Some(attr) = ndx.get(&nuclide) {
+                        if kept.insert(nuclide) {
+                            queue.extend(attr.progeny.iter().map(|p| p.nuclide));
+                        }
+                    }
+                }
+                ndx.retain(|nuclide, _| kept.contains(nuclide));
+            }
+            Ok(ndx)
+        })
     }
 
     pub fn rad(&self) -> Result<&HashMap<Nuclide, Vec<rad::RadSpectrum>>, Error> {
-        self.rad
-            .get_or_try_init(|| SpectrumReader::new(&self.path.join("ICRP-07.RAD"))?.read())
+        self.rad.get_or_try_init(|| {
+            SpectrumReader::new(&self.path.join("ICRP-07.RAD"))?
+                .read()
+                .and_then(|m| self.retain(m))
+        })
     }
 
     pub fn bet(&self) -> Result<&HashMap<Nuclide, Vec<bet::BetSpectrum>>, Error> {
-        self.bet
-            .get_or_try_init(|| SpectrumReader::new(&self.path.join("ICRP-07.BET"))?.read())
+        self.bet.get_or_try_init(|| {
+            SpectrumReader::new(&self.path.join("ICRP-07.BET"))?
+                .read()
+                .and_then(|m| self.retain(m))
+        })
     }
 
     pub fn ack(&self) -> Result<&HashMap<Nuclide, Vec<ack::AckSpectrum>>, Error> {
-        self.ack
-            .get_or_try_init(|| SpectrumReader::new(&self.path.join("ICRP-07.ACK"))?.read())
+        self.ack.get_or_try_init(|| {
+            SpectrumReader::new(&self.path.join("ICRP-07.ACK"))?
+                .read()
+                .and_then(|m| self.retain(m))
+        })
     }
 
     pub fn nsf(&self) -> Result<&HashMap<Nuclide, Vec<nsf::NsfSpectrum>>, Error> {
-        self.nsf
-            .get_or_try_init(|| SpectrumReader::new(&self.path.join("ICRP-07.NSF"))?.read())
+        self.nsf.get_or_try_init(|| {
+            SpectrumReader::new(&self.path.join("ICRP-07.NSF"))?
+                .read()
+                .and_then(|m| self.retain(m))
+        })
     }
-}
 
-impl NuclideProgeny for Icrp107 {
-    fn progeny(&self, nuclide: Nuclide) -> Result<Vec<Progeny>, Error> {
+    /// Read NDX and all spectrum files concurrently, so that later accesses are cached
+    ///
+    /// Each file is read at most once even if accessed from other threads meanwhile.
+    /// The first error in the order of NDX, RAD, BET, ACK and NSF is returned, while
+    /// files read successfully stay cached.
+    pub fn preload_all(&self) -> Result<(), Error> {
+        std::thread::scope(|s| {
+            let handles = [
+                s.spawn(|| self.ndx().map(|_| ())),
+                s.spawn(|| self.rad().map(|_| ())),
+                s.spawn(|| self.bet().map(|_| ())),
+                s.spawn(|| self.ack().map(|_| ())),
+                s.spawn(|| self.nsf().map(|_| ())),
+            ];
+            handles
+                .into_iter()
+                .try_for_each(|h| h.join().unwrap_or_else(|e| std::panic::resume_unwind(e)))
+        })
+    }
+
+    /// RAD lines of nuclide sorted by energy, empty if the nuclide is absent
+    pub fn rad_sorted(
+        &self,
+        nuclide: Nuclide,
+        order: SortOrder,
+    ) -> Result<Vec<&rad::RadSpectrum>, Error> {
+        Ok(self
+            .rad()?
+            .get(&nuclide)
+            .map_or_else(Vec::new, |s| sort_by_energy(s, |l| l.energy, order)))
+    }
+
+    /// BET grid points of nuclide sorted by energy, empty if the nuclide is absent
+    pub fn bet_sorted(
+        &self,
+        nuclide: Nuclide,
+        order: SortOrder,
+    ) -> Result<Vec<&bet::BetSpectrum>, Error> {
+        Ok(self
+            .bet()?
+            .get(&nuclide)
+            .map_or_else(Vec::new, |s| sort_by_energy(s, |l| l.energy, order)))
+    }
+
+    /// ACK lines of nuclide sorted by energy, empty if the nuclide is absent
+    pub fn ack_sorted(
+        &self,
+        nuclide: Nuclide,
+        order: SortOrder,
+    ) -> Result<Vec<&ack::AckSpectrum>, Error> {
+        Ok(self
+            .ack()?
+            .get(&nuclide)
+            .map_or_else(Vec::new, |s| sort_by_energy(s, |l| l.energy, order)))
+    }
+
+    /// NSF bins of nuclide sorted by lower energy, empty if the nuclide is absent
+    pub fn nsf_sorted(
+        &self,
+        nuclide: Nuclide,
+        order: SortOrder,
+    ) -> Result<Vec<&nsf::NsfSpectrum>, Error> {
+        Ok(self
+            .nsf()?
+            .get(&nuclide)
+            .map_or_else(Vec::new, |s| sort_by_energy(s, |l| l.energy_lower, order)))
+    }
+
+    // Reverse index of progeny: daughter -> parents
+    fn parents(&self) -> Result<&HashMap<Nuclide, Vec<Nuclide>>, Error> {
+        self.parents.get_or_try_init(|| {
+            let mut parents: HashMap<Nuclide, Vec<Nuclide>> = HashMap::new();
+            for (&parent, attr) in self.ndx()? {
+                for daughter in &attr.progeny {
+                    parents.entry(daughter.nuclide).or_default().push(parent);
+                }
+            }
+            for p in parents.values_mut() {
+                p.sort();
+                p.dedup();
+            }
+            Ok(parents)
+        })
+    }
+
+    // Decay constants (s-1) of indexed nuclides, under the year convention of dataset
+    fn lambdas(&self) -> Result<&HashMap<Nuclide, f64>, Error> {
+        self.lambdas.get_or_try_init(|| {
+            self.ndx()?
+                .keys()
+                .map(|&nuclide| {
+                    let half_life = self.under_year_convention(self.half_life(nuclide)?);
+                    Ok((nuclide, half_life.as_lambda()))
+                })
+                .collect()
+        })
+    }
+
+    // Half-life with years converted to days of the year convention of dataset, from which
+    // seconds are derived, as half-lives are kept in the unit of index
+    fn under_year_convention(&self, half_life: HalfLife) -> HalfLife {
+        match half_life.unit {
+            TimeUnit::Year if self.year_convention != YearConvention::Tropical => {
+                let days = self.year_convention.days();
+                HalfLife {
+                    value: half_life.value * days,
+                    unit: TimeUnit::Day,
+                    uncertainty: half_life.uncertainty.map(|u| u * days),
+                }
+            }
+            _ => half_life,
+        }
+    }
+
+    /// Nuclides decaying directly into nuclide
+    pub fn parents_of(&self, nuclide: Nuclide) -> Result<Vec<Nuclide>, Error> {
+        Ok(self.parents()?.get(&nuclide).cloned().unwrap_or_default())
+    }
+
+    /// Drop loaded data, which is read from files again on next access
+    pub fn reload(&mut self) {
+        self.ndx.take();
+        self.rad.take();
+        self.bet.take();
+        self.ack.take();
+        self.nsf.take();
+        self.parents.take();
+        self.lambdas.take();
+    }
+
+    /// Progeny of nuclide borrowed from the loaded index, without cloning
+    ///
+    /// This is the same as [`NuclideProgeny::progeny`] for walking chains in hot loops.
+    pub fn progeny_ref(&self, nuclide: Nuclide) -> Result<&[Progeny], Error> {
         self.ndx()?
             .get(&nuclide)
-            .map(|attr| attr.progeny.clone())
+            .map(|attr| attr.progeny.as_slice())
             .ok_or_else(|| Error::InvalidNuclide(nuclide.to_string()))
     }
+
+    /// RAD lines of nuclide, which are empty for nuclides absent from RAD
+    pub fn rad_of(&self, nuclide: Nuclide) -> Result<&[rad::RadSpectrum], Error> {
+        self.spectrum_of(nuclide, || self.rad())
+    }
+
+    /// BET grid points of nuclide, which are empty for nuclides absent from BET
+    pub fn bet_of(&self, nuclide: Nuclide) -> Result<&[bet::BetSpectrum], Error> {
+        self.spectrum_of(nuclide, || self.bet())
+    }
+
+    /// ACK lines of nuclide, which are empty for nuclides absent from ACK
+    pub fn ack_of(&self, nuclide: Nuclide) -> Result<&[ack::AckSpectrum], Error> {
+        self.spectrum_of(nuclide, || self.ack())
+    }
+
+    /// NSF bins of nuclide, which are empty for nuclides absent from NSF
+    pub fn nsf_of(&self, nuclide: Nuclide) -> Result<&[nsf::NsfSpectrum], Error> {
+        self.spectrum_of(nuclide, || self.nsf())
+    }
+
+    // Spectrum of nuclide in NDX, read from the whole spectrum file on first access
+    fn spectrum_of<'a, T>(
+        &'a self,
+        nuclide: Nuclide,
+        spectra: impl FnOnce() -> Result<&'a HashMap<Nuclide, Vec<T>>, Error>,
+    ) -> Result<&'a [T], Error> {
+        if self.ndx()?.contains_key(&nuclide) {
+            Ok(spectra()?.get(&nuclide).map_or(&[], |s| s.as_slice()))
+        } else {
+            Err(Error::InvalidNuclide(nuclide.to_string()))
+        }
+    }
+
+    /// Air-kerma rate constant of root and its progeny in secular equilibrium
+    ///
+    /// The constant of each member is weighted by its activity relative to root, which is
+    /// the cumulative branching yield in equilibrium.
+    pub fn chain_air_kerma_constant(&self, root: Nuclide) -> Result<f64, Error> {
+        let ndx = self.ndx()?;
+        Ok(self
+            .cumulative_yields(root)?
+            .into_iter()
+            .filter_map(|(nuclide, y)| ndx.get(&nuclide).map(|attr| y * attr.air_kerma_const))
+            .sum())
+    }
+
+    /// Effective half-life of nuclide retained in the body with `biological` half-life
+    pub fn effective_half_life(
+        &self,
+        nuclide: Nuclide,
+        biological: HalfLife,
+    ) -> Result<HalfLife, Error> {
+        let half_life = self.half_life(nuclide)?;
+        if biological.is_stable() {
+            Ok(half_life)
+        } else {
+            Ok(self.under_year_convention(half_life).effective(biological))
+        }
+    }
+
+    /// Absorbed dose rate (Gy/s) in an infinite medium of uniform concentration (Bq/kg)
+    ///
+    /// All energy emitted as alpha, electron and photon radiation (neutrinos excluded)
+    /// is assumed to be absorbed in the medium, i.e. absorbed fraction is 1.
+    pub fn infinite_medium_dose_rate(
+        &self,
+        nuclide: Nuclide,
+        concentration_bq_per_kg: f64,
+    ) -> Result<f64, Error> {
+        let attr = self
+            .ndx()?
+            .get(&nuclide)
+            .ok_or_else(|| Error::InvalidNuclide(nuclide.to_string()))?;
+        let energy = attr.alpha_energy + attr.electron_energy + attr.photon_energy;
+
+        Ok(concentration_bq_per_kg * energy * MEV_TO_J)
+    }
+
+    /// Partition of decay energy (MeV per nuclear transformation) of nuclide
+    ///
+    /// ICRP-07 has no Q-values, so the decay energy is taken as the emitted energy plus
+    /// that of neutrinos of beta decay, i.e. the BET endpoint for each beta particle less
+    /// its energy. All beta particles are thus assumed to share the highest endpoint, so
+    /// the neutrino remainder includes excitation left in daughters by lower branches,
+    /// e.g. Ba-137m of Cs-137. Neutrinos of electron capture and recoil are neglected.
+    pub fn energy_partition(&self, nuclide: Nuclide) -> Result<EnergyPartition, Error> {
+        let attr = self
+            .ndx()?
+            .get(&nuclide)
+            .ok_or_else(|| Error::InvalidNuclide(nuclide.to_string()))?;
+
+        let points = self.beta_points(nuclide)?;
+        let (number, beta_energy) = emission::beta_moments(&points);
+        let endpoint = points.iter().map(|p| p.0).fold(0., f64::max);
+        let emitted = attr.alpha_energy + attr.electron_energy + attr.photon_energy;
+
+        EnergyPartition::new(
+            emitted + number * endpoint - beta_energy,
+            attr.alpha_energy,
+            attr.electron_energy,
+            attr.photon_energy,
+        )
+    }
+
+    /// Indexed nuclides sorted by atomic number, mass number and metastable state
+    pub fn nuclides(&self) -> Result<impl Iterator<Item = Nuclide>, Error> {
+        let mut nuclides: Vec<Nuclide> = self.ndx()?.keys().copied().collect();
+        nuclides.sort();
+        Ok(nuclides.into_iter())
+    }
+
+    /// Indexed isotopes of element symbol, e.g. "Cs", sorted by mass number and state
+    pub fn isotopes_of(&self, symbol: &str) -> Result<Vec<Nuclide>, Error> {
+        let z = atomic_number(symbol).ok_or_else(|| Error::InvalidSymbol(symbol.to_string()))?;
+        Ok(self.nuclides()?.filter(|n| n.z() == Some(z)).collect())
+    }
+
+    /// Indexed nuclides of mass number within `lo..=hi`, sorted by mass number
+    pub fn nuclides_in_mass_range(&self, lo: u16, hi: u16) -> Result<Vec<Nuclide>, Error> {
+        let range = u32::from(lo)..=u32::from(hi);
+        let mut nuclides: Vec<Nuclide> = self
+            .nuclides()?
+            .filter(|n| n.a().is_some_and(|a| range.contains(&a)))
+            .collect();
+        nuclides.sort_by_key(|n| (n.a(), *n));
+        Ok(nuclides)
+    }
+
+    /// Whether nuclide is indexed, false if the index cannot be loaded
+    pub fn contains(&self, nuclide: Nuclide) -> bool {
+        self.ndx().is_ok_and(|ndx| ndx.contains_key(&nuclide))
+    }
+
+    /// Fold over all indexed nuclides in ascending order of nuclide
+    pub fn fold_nuclides<T, F>(&self, init: T, mut f: F) -> Result<T, Error>
+    where
+        F: FnMut(T, &Nuclide, &ndx::Attribute) -> T,
+    {
+        let ndx = self.ndx()?;
+        let mut nuclides: Vec<&Nuclide> = ndx.keys().collect();
+        nuclides.sort();
+
+        Ok(nuclides
+            .into_iter()
+            .fold(init, |acc, nuclide| f(acc, nuclide, &ndx[nuclide])))
+    }
+
+    /// Nuclides without progeny in the dataset, flagged whether stable per half-life
+    ///
+    /// Decay products absent in the index are stable, as only radionuclides are indexed.
+    /// Indexed nuclides without progeny and fission products are terminal only because
+    /// the data ends there. Results are sorted by nuclide.
+    pub fn terminal_nuclides(&self) -> Result<Vec<(Nuclide, bool)>, Error> {
+        let ndx = self.ndx()?;
+        let mut terminals = HashMap::new();
+
+        for (&nuclide, attr) in ndx {
+            if attr.progeny.is_empty() {
+                terminals.insert(nuclide, attr.half_life.as_sec().is_infinite());
+            }
+            for daughter in &attr.progeny {
+                if !ndx.contains_key(&daughter.nuclide) {
+                    let stable = daughter.nuclide != Nuclide::FissionProducts;
+                    terminals.insert(daughter.nuclide, stable);
+                }
+            }
+        }
+
+        let mut terminals: Vec<(Nuclide, bool)> = terminals.into_iter().collect();
+        terminals.sort();
+
+        Ok(terminals)
+    }
+
+    /// Radioactive nuclides with their specific activity (Bq/g), sorted by specific activity
+    pub fn nuclides_by_specific_activity(
+        &self,
+        descending: bool,
+    ) -> Result<Vec<(Nuclide, f64)>, Error> {
+        let mut nuclides = vec![];
+        for &nuclide in self.ndx()?.keys() {
+            let activity = self.specific_activity(nuclide)?;
+            if activity > 0. {
+                nuclides.push((nuclide, activity));
+            }
+        }
+
+        nuclides.sort_by(|(a, a_act), (b, b_act)| {
+            let ord = a_act.total_cmp(b_act).then_with(|| a.cmp(b));
+            if descending {
+                ord.reverse()
+            } else {
+                ord
+            }
+        });
+
+        Ok(nuclides)
+    }
+}
+
+impl NuclideProgeny for Icrp107 {
+    fn progeny(&self, nuclide: Nuclide) -> Result<Vec<Progeny>, Error> {
+        self.progeny_ref(nuclide).map(|p| p.to_vec())
+    }
 }
 
 impl NuclideHalfLife for Icrp107 {
@@ -82,6 +564,38 @@ impl NuclideHalfLife for Icrp107 {
             .map(|attr| attr.half_life)
             .ok_or_else(|| Error::InvalidNuclide(nuclide.to_string()))
     }
+
+    fn decay_constant(&self, nuclide: Nuclide) -> Result<f64, Error> {
+        self.lambdas()?
+            .get(&nuclide)
+            .copied()
+            .ok_or_else(|| Error::InvalidNuclide(nuclide.to_string()))
+    }
+
+    fn lambda_with_uncertainty(&self, nuclide: Nuclide) -> Result<(f64, f64), Error> {
+        self.half_life(nuclide)
+            .map(|hl| self.under_year_convention(hl).as_lambda_with_uncertainty())
+    }
+
+    fn decay_time_for_n_half_lives(&self, nuclide: Nuclide, n: f64) -> Result<HalfLife, Error> {
+        let secs = self
+            .under_year_convention(self.half_life(nuclide)?)
+            .as_sec();
+        if secs.is_finite() {
+            Ok(HalfLife::from_secs(n * secs))
+        } else {
+            Err(Error::InvalidHalfLife(format!("{} is stable", nuclide)))
+        }
+    }
+}
+
+impl NuclideMass for Icrp107 {
+    fn nuclide_mass(&self, nuclide: Nuclide) -> Result<f64, Error> {
+        self.ndx()?
+            .get(&nuclide)
+            .map(|attr| attr.amu)
+            .ok_or_else(|| Error::InvalidNuclide(nuclide.to_string()))
+    }
 }
 
 impl NuclideDecayMode for Icrp107 {
@@ -92,3 +606,1007 @@ impl NuclideDecayMode for Icrp107 {
             .ok_or_else(|| Error::InvalidNuclide(nuclide.to_string()))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::primitive::{DecayConstant, DecayMode};
+    use spectrum::RadiationType;
+
+    const DATA_PATH: &str = "data/icrp107";
+
+    const NDX_HEADER: &str = "ICRP-07.NDX test fixture\n";
+    const NDX_AC226: &str = "Ac-226    29.37h B-ECA      1944      1      0     0 Th-226   1108 8.3000E-01 Ra-226    822 1.7000E-01 Fr-222    361 6.0000E-05             0        0.0  0.0003 0.29143 0.13271  14 140   5   99   1 226.026097 1.048E-171.048E-17\n";
+    const NDX_RA226: &str = "Ra-226      1600y A                                  Rn-222      0 1.0000E+00             0        0.0             0        0.0             0        0.0  4.7741 0.00363 0.00674   0   4   0    2   2 226.025410 8.170E-198.170E-19\n";
+
+    const NDX_RN222: &str = "Rn-222    3.8235d A                                  Po-218      0 1.0000E+00             0        0.0             0        0.0             0        0.0  4.7741 0.00363 0.00674   0   4   0    2   2 222.017578 2.225E-212.225E-21\n";
+
+    const NDX_H3: &str = "H-3       12.312y B-                                 He-3        0 1.0000E+00             0        0.0             0        0.0             0        0.0  0.0000 0.00568 0.00000   0   0   1    1   0   3.016049 0.000E+000.000E+00\n";
+
+    const NDX_CO60: &str = "Co-60     5.2713y B-                                 Ni-60       0 1.0000E+00             0        0.0             0        0.0             0        0.0  0.0000 0.09665 2.50377   0   0   1    2   0  59.933817 8.670E-178.670E-17\n";
+
+    const NDX_CS137: &str = "Cs-137   30.1671y B-                                 Ba-137m     0 9.4400E-01 Ba-137      0 5.6000E-02             0        0.0             0        0.0  0.0000 0.18684 0.00000   0   0   2    5   0 136.907089 2.800E-192.800E-19\n";
+    const NDX_BA137: &str = "Ba-137    Stable  IT                                             0        0.0             0        0.0             0        0.0             0        0.0  0.0000 0.00000 0.00000   0   0   0    0   0 136.905827 0.000E+000.000E+00\n";
+    const NDX_BA137M: &str = "Ba-137m    2.552m IT                                 Ba-137      0 1.0000E+00             0        0.0             0        0.0             0        0.0  0.0000 0.06481 0.59807   7   3   0   10   0 136.906000 2.297E-172.297E-17\n";
+
+    // Dataset directory with an index of given NDX lines only, without files of earlier runs
+    fn fixture(name: &str, lines: &[&str]) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("radioactive-icrp107-{}", name));
+        if path.exists() {
+            std::fs::remove_dir_all(&path).unwrap();
+        }
+        std::fs::create_dir_all(&path).unwrap();
+        std::fs::write(
+            path.join("ICRP-07.NDX"),
+            [&[NDX_HEADER], lines].concat().concat(),
+        )
+        .unwrap();
+        path
+    }
+
+    // Add RAD file of given (nuclide, lines) to dataset directory
+    fn write_rad(path: &Path, spectra: &[(&str, &[&str])]) {
+        let mut rad = String::new();
+        for (nuclide, lines) in spectra {
+            rad.push_str(&format!("{:<7}{:>13}\n", nuclide, lines.len()));
+            for line in lines.iter() {
+                rad.push_str(line);
+                rad.push('\n');
+            }
+        }
+        std::fs::write(path.join("ICRP-07.RAD"), rad).unwrap();
+    }
+
+    #[test]
+    fn implements_decay_traits() {
+        fn decay_data<T>()
+        where
+            T: NuclideProgeny
+                + NuclideHalfLife
+                + NuclideDecayMode
+                + NuclideMass
+                + DecayConstant
+                + SpecificActivity
+                + DecaySeries
+                + crate::decaychain::ChainTree
+                + Send
+                + Sync,
+        {
+        }
+        decay_data::<Icrp107>();
+
+        let path = fixture("decay-traits", &[NDX_CS137, NDX_BA137M]);
+        let data = std::sync::Arc::new(Icrp107::open(&path).unwrap());
+        let chain =
+            crate::decaychain::DecayChainBuilder::new(data).build("Cs-137".parse().unwrap());
+        assert_eq!(chain.node_count(), 3);
+    }
+
+    #[test]
+    fn ndx_only() {
+        let path = fixture("ndx-only", &[NDX_AC226, NDX_RA226]);
+        let data = Icrp107::open(&path).unwrap();
+        let ac226: Nuclide = "Ac-226".parse().unwrap();
+
+        assert_eq!(data.half_life(ac226).unwrap(), "29.37h".parse().unwrap());
+        assert_eq!(data.progeny(ac226).unwrap().len(), 3);
+        assert!(data.lambda(ac226).unwrap() > 0.);
+
+        match data.rad() {
+            Err(Error::FileNotFound(file)) => assert_eq!(file, path.join("ICRP-07.RAD")),
+            _ => panic!("RAD should be missing"),
+        }
+        assert!(matches!(data.bet(), Err(Error::FileNotFound(_))));
+        assert!(matches!(data.ack(), Err(Error::FileNotFound(_))));
+        assert!(matches!(data.nsf(), Err(Error::FileNotFound(_))));
+    }
+
+    #[test]
+    fn cached_lambda() {
+        let path = fixture("cached-lambda", &[NDX_CS137, NDX_BA137M, NDX_BA137]);
+        let mut data = Icrp107::builder()
+            .path(&path)
+            .year_convention(YearConvention::Julian)
+            .build()
+            .unwrap();
+        let cs137: Nuclide = "Cs-137".parse().unwrap();
+
+        let lambda = data.lambda(cs137).unwrap();
+        assert_eq!(lambda.to_bits(), data.lambda(cs137).unwrap().to_bits());
+        let expected = 2.0_f64.ln() / (30.1671 * 365.25 * 86_400.);
+        assert!((lambda / expected - 1.).abs() < 1e-12);
+        assert!(std::ptr::eq(
+            data.lambdas().unwrap(),
+            data.lambdas().unwrap()
+        ));
+
+        let ba137: Nuclide = "Ba-137".parse().unwrap();
+        assert!(data.half_life(ba137).unwrap().is_stable());
+        assert_eq!(data.lambda(ba137).unwrap(), 0.);
+        assert_eq!(data.lambda(ba137).unwrap(), 0.);
+
+        assert!(matches!(
+            data.lambda("Co-60".parse().unwrap()),
+            Err(Error::InvalidNuclide(_))
+        ));
+
+        data.reload();
+        assert!(data.lambdas.get().is_none());
+        assert_eq!(data.lambda(cs137).unwrap().to_bits(), lambda.to_bits());
+    }
+
+    #[test]
+    fn gzip_compressed() {
+        use flate2::write::GzEncoder;
+        use std::io::Write;
+
+        let plain = fixture("ndx-plain", &[NDX_AC226, NDX_RA226]);
+        let gz = fixture("ndx-gz", &[NDX_CS137]);
+        let ndx = std::fs::read(plain.join("ICRP-07.NDX")).unwrap();
+        let mut encoder = GzEncoder::new(vec![], flate2::Compression::default());
+        encoder.write_all(&ndx).unwrap();
+        std::fs::write(gz.join("ICRP-07.NDX.gz"), encoder.finish().unwrap()).unwrap();
+
+        let sorted = |data: &Icrp107| {
+            let ndx: std::collections::BTreeMap<_, _> = data.ndx().unwrap().iter().collect();
+            format!("{:?}", ndx)
+        };
+
+        // plain file is preferred
+        let data = Icrp107::open(&gz).unwrap();
+        assert_eq!(data.ndx().unwrap().len(), 1);
+        assert!(data.contains("Cs-137".parse().unwrap()));
+
+        std::fs::remove_file(gz.join("ICRP-07.NDX")).unwrap();
+        let data = Icrp107::open(&gz).unwrap();
+        assert_eq!(sorted(&data), sorted(&Icrp107::open(&plain).unwrap()));
+
+        match data.rad() {
+            Err(Error::FileNotFound(file)) => assert_eq!(file, gz.join("ICRP-07.RAD")),
+            _ => panic!("RAD should be missing"),
+        }
+    }
+
+    #[test]
+    fn parents_of() {
+        let path = fixture("parents", &[NDX_AC226, NDX_RA226]);
+        let mut data = Icrp107::open(&path).unwrap();
+        let ac226: Nuclide = "Ac-226".parse().unwrap();
+
+        assert_eq!(
+            data.parents_of("Th-226".parse().unwrap()).unwrap(),
+            vec![ac226]
+        );
+        assert_eq!(
+            data.parents_of("Ra-226".parse().unwrap()).unwrap(),
+            vec![ac226]
+        );
+        assert!(data.parents_of(ac226).unwrap().is_empty());
+
+        let index = data.parents().unwrap() as *const _;
+        data.parents_of("Rn-222".parse().unwrap()).unwrap();
+        assert!(std::ptr::eq(index, data.parents().unwrap()));
+
+        data.reload();
+        assert!(data.parents.get().is_none());
+        assert!(data.ndx.get().is_none());
+        assert_eq!(data.parents_of("Rn-222".parse().unwrap()).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn chain_air_kerma_constant() {
+        let path = fixture("air-kerma", &[NDX_CS137, NDX_BA137M]);
+        let data = Icrp107::open(&path).unwrap();
+
+        let constant = data
+            .chain_air_kerma_constant("Cs-137".parse().unwrap())
+            .unwrap();
+        assert!((constant - (2.8e-19 + 0.944 * 2.297e-17)).abs() < 1e-30);
+
+        let constant = data
+            .chain_air_kerma_constant("Ba-137m".parse().unwrap())
+            .unwrap();
+        assert_eq!(constant, 2.297e-17);
+    }
+
+    #[test]
+    fn effective_half_life() {
+        let path = fixture("effective-half-life", &[NDX_CS137, NDX_BA137]);
+        let data = Icrp107::open(&path).unwrap();
+        let biological: HalfLife = "110 d".parse().unwrap();
+
+        let t = data
+            .effective_half_life("Cs-137".parse().unwrap(), biological)
+            .unwrap();
+        assert_eq!(t.unit, TimeUnit::Day);
+        assert!(t.value < 110. && t.value > 108.);
+
+        let t = data
+            .effective_half_life("Ba-137".parse().unwrap(), biological)
+            .unwrap();
+        assert_eq!(t.value, 110.);
+        assert!(data
+            .effective_half_life("Co-60".parse().unwrap(), biological)
+            .is_err());
+    }
+
+    #[test]
+    fn infinite_medium_dose_rate() {
+        let path = fixture("infinite-medium", &[NDX_CS137]);
+        let data = Icrp107::open(&path).unwrap();
+
+        let rate = data
+            .infinite_medium_dose_rate("Cs-137".parse().unwrap(), 1e3)
+            .unwrap();
+        assert!((rate - 1e3 * 0.18684 * 1.602176634e-13).abs() < 1e-24);
+
+        assert!(data
+            .infinite_medium_dose_rate("Co-60".parse().unwrap(), 1e3)
+            .is_err());
+    }
+
+    #[test]
+    fn wallet_card() {
+        let path = fixture("wallet-card", &[NDX_CS137, NDX_BA137M]);
+        write_rad(
+            &path,
+            &[
+                (
+                    "Cs-137",
+                    &[
+                        " 2  9.4400E-01  1.7432E-01B- ",
+                        " 2  5.6000E-02  4.1635E-01B- ",
+                    ],
+                ),
+                ("Ba-137m", &[" 1  8.9900E-01  6.6166E-01G  "]),
+            ],
+        );
+        let data = Icrp107::open(&path).unwrap();
+
+        let card = data.wallet_card("Cs-137".parse().unwrap()).unwrap();
+        assert!(card.starts_with("Cs-137\n"));
+        assert!(card.contains("30.1671 y"));
+        assert!(card.contains("G  0.661660 MeV 84.87% (Ba-137m)"));
+
+        assert!(data.wallet_card("Co-60".parse().unwrap()).is_err());
+    }
+
+    #[test]
+    fn energy_per_decay() {
+        let path = fixture("energy-per-decay", &[NDX_CS137, NDX_BA137M]);
+        write_rad(
+            &path,
+            &[(
+                "Cs-137",
+                &[
+                    " 2  9.4400E-01  1.7432E-01B- ",
+                    " 2  5.6000E-02  4.1635E-01B- ",
+                    " 1  8.5100E-01  6.6166E-01G  ",
+                    " 5  3.6400E-02  3.2194E-02X  ",
+                ],
+            )],
+        );
+        let data = Icrp107::open(&path).unwrap();
+        let cs137 = "Cs-137".parse().unwrap();
+
+        // 661.7 keV gamma emitted in 85.1% of decays
+        assert_eq!(
+            data.mean_energy(cs137, RadiationType::Gamma).unwrap(),
+            (0.851 * 0.66166e6f64).round() as u32
+        );
+        assert_eq!(
+            data.mean_energy(cs137, RadiationType::X).unwrap(),
+            (0.0364 * 0.032194e6f64).round() as u32
+        );
+        assert_eq!(
+            data.mean_energy(cs137, RadiationType::PromptGamma).unwrap(),
+            0
+        );
+
+        let total = 0.944 * 0.17432 + 0.056 * 0.41635 + 0.851 * 0.66166 + 0.0364 * 0.032194;
+        assert!((data.total_energy_per_decay(cs137).unwrap() - total).abs() < 1e-12);
+
+        // in NDX but not in RAD
+        let ba137m = "Ba-137m".parse().unwrap();
+        assert_eq!(data.total_energy_per_decay(ba137m).unwrap(), 0.);
+        assert_eq!(data.mean_energy(ba137m, RadiationType::Gamma).unwrap(), 0);
+
+        assert!(data
+            .total_energy_per_decay("Co-60".parse().unwrap())
+            .is_err());
+    }
+
+    #[test]
+    fn validate_against_ndx() {
+        let path = fixture("validate", &[NDX_CS137, NDX_BA137M, NDX_RA226]);
+        let cs137 = (
+            "Cs-137",
+            &[
+                " 2  9.4400E-01  1.7432E-01B- ",
+                " 2  5.6000E-02  4.1635E-01B- ",
+            ][..],
+        );
+        let ra226 = ("Ra-226", &[" 1  9.4450E-01  4.7843E+00A  "][..]);
+        let ba137m_rad = |gamma| {
+            [
+                gamma,
+                " 5  3.6400E-02  3.2194E-02X  ",
+                " 3  7.7900E-02  6.2420E-01IE ",
+                " 3  2.4700E-02  6.5570E-01IE ",
+            ]
+        };
+
+        write_rad(
+            &path,
+            &[
+                cs137,
+                ("Ba-137m", &ba137m_rad(" 1  8.9900E-01  6.6166E-01G  ")[..]),
+                ra226,
+            ],
+        );
+        let warnings = Icrp107::open(&path).unwrap().validate().unwrap();
+        // minor alpha line and other emissions of Ra-226 are missing
+        assert_eq!(warnings.len(), 3);
+        assert!(warnings
+            .iter()
+            .all(|w| w.nuclide == "Ra-226".parse().unwrap()));
+        assert_eq!(warnings[0].quantity, NdxQuantity::AlphaEnergy);
+        assert!((warnings[0].relative_error + 0.0534).abs() < 1e-3);
+
+        // yield of the gamma line off by a digit
+        write_rad(
+            &path,
+            &[
+                cs137,
+                ("Ba-137m", &ba137m_rad(" 1  8.9900E-02  6.6166E-01G  ")[..]),
+            ],
+        );
+        let warnings = Icrp107::open(&path).unwrap().validate().unwrap();
+        let ba137m: Nuclide = "Ba-137m".parse().unwrap();
+        let warning = warnings.iter().find(|w| w.nuclide == ba137m).unwrap();
+        assert_eq!(warning.quantity, NdxQuantity::PhotonEnergy);
+        assert_eq!(warning.expected, 0.59807);
+        let actual = 0.0899 * 0.66166 + 0.0364 * 0.032194;
+        assert!((warning.actual - actual).abs() < 1e-12);
+        assert!((warning.relative_error - (actual / 0.59807 - 1.)).abs() < 1e-12);
+        assert!(warning
+            .to_string()
+            .starts_with("Ba-137m: photon energy is "));
+        assert!(!warnings
+            .iter()
+            .any(|w| w.nuclide == "Cs-137".parse().unwrap()));
+    }
+
+    #[test]
+    fn nuclides() {
+        let data = Icrp107::open(fixture("nuclides", &[NDX_RA226, NDX_BA137M, NDX_CS137])).unwrap();
+
+        let nuclides: Vec<Nuclide> = data.nuclides().unwrap().collect();
+        assert_eq!(nuclides.len(), data.ndx().unwrap().len());
+        assert_eq!(
+            nuclides,
+            ["Cs-137", "Ba-137m", "Ra-226"]
+                .iter()
+                .map(|n| n.parse().unwrap())
+                .collect::<Vec<Nuclide>>()
+        );
+
+        assert!(data.contains("Ba-137m".parse().unwrap()));
+        assert!(!data.contains("Ba-137".parse().unwrap()));
+        assert!(!data.contains("Xe-300".parse().unwrap()));
+    }
+
+    #[test]
+    fn instances_cache_separately() {
+        let cs = Icrp107::open(fixture("cache-cs", &[NDX_CS137, NDX_BA137M])).unwrap();
+        let ra = Icrp107::open(fixture("cache-ra", &[NDX_RA226])).unwrap();
+
+        assert_eq!(cs.ndx().unwrap().len(), 2);
+        assert_eq!(ra.ndx().unwrap().len(), 1);
+        assert!(ra.ndx().unwrap().contains_key(&"Ra-226".parse().unwrap()));
+        assert!(std::ptr::eq(cs.ndx().unwrap(), cs.ndx().unwrap()));
+
+        let again = Icrp107::open(fixture("cache-cs", &[NDX_CS137, NDX_BA137M])).unwrap();
+        assert!(!std::ptr::eq(cs.ndx().unwrap(), again.ndx().unwrap()));
+    }
+
+    #[test]
+    fn gamma_lines() {
+        let path = fixture("gamma-lines", &[NDX_CS137, NDX_BA137M]);
+        write_rad(
+            &path,
+            &[
+                ("Cs-137", &[" 2  9.4400E-01  1.7432E-01B- "]),
+                ("Ba-137m", &[" 1  8.9900E-01  6.6166E-01G  "]),
+            ],
+        );
+        let data = Icrp107::open(&path).unwrap();
+
+        let lines = data
+            .gamma_lines("Ba-137m".parse().unwrap(), 0.01, false)
+            .unwrap();
+        assert_eq!(lines, vec![(661_660, 0.899)]);
+        assert!(data
+            .gamma_lines("Cs-137".parse().unwrap(), 0., true)
+            .unwrap()
+            .is_empty());
+        assert!(matches!(
+            data.gamma_lines("Co-60".parse().unwrap(), 0., true),
+            Err(Error::InvalidNuclide(_))
+        ));
+    }
+
+    #[test]
+    fn binned_spectra() {
+        let path = fixture("binned-spectra", &[NDX_CS137, NDX_BA137M]);
+        write_rad(
+            &path,
+            &[(
+                "Ba-137m",
+                &[
+                    " 6  3.6400E-02  3.1817E-02X  ",
+                    " 1  8.9900E-01  6.6166E-01G  ",
+                ],
+            )],
+        );
+        std::fs::write(
+            path.join("ICRP-07.BET"),
+            "Cs-137       3\n0.0000 0.0000E+00\n0.2000 4.0000E+00\n0.5000 0.0000E+00\n",
+        )
+        .unwrap();
+        let data = Icrp107::open(&path).unwrap();
+        let cs137: Nuclide = "Cs-137".parse().unwrap();
+        let ba137m: Nuclide = "Ba-137m".parse().unwrap();
+
+        let hist = data.binned_gamma(ba137m, &[0., 100e3, 661_660.]).unwrap();
+        assert_eq!(hist.yields, vec![0.0364, 0.]);
+        assert_eq!(hist.dropped, 1);
+        assert_eq!(
+            data.binned_gamma(cs137, &[0., 1e6]).unwrap().yields,
+            vec![0.]
+        );
+
+        // triangular spectrum of (0, 0.2, 0.5) MeV has its mean at 0.7 / 3 MeV
+        assert!((data.beta_mean_energy(cs137).unwrap() - 0.7 / 3.).abs() < 1e-12);
+        assert_eq!(data.beta_endpoint_energy(cs137).unwrap(), 0.5);
+        assert_eq!(data.beta_mean_energy(ba137m).unwrap(), 0.);
+        assert_eq!(data.beta_endpoint_energy(ba137m).unwrap(), 0.);
+
+        let bins = data.binned_beta(cs137, &[0., 200e3, 1e6]).unwrap();
+        assert!((bins[0] - 0.4).abs() < 1e-12);
+        assert!((bins[1] - 0.6).abs() < 1e-12);
+        assert_eq!(data.binned_beta(ba137m, &[0., 1e6]).unwrap(), vec![0.]);
+    }
+
+    #[test]
+    fn export_spectrum_csv() {
+        let path = fixture("spectrum-csv", &[NDX_CS137, NDX_BA137M, NDX_RA226]);
+        write_rad(
+            &path,
+            &[
+                (
+                    "Cs-137",
+                    &[
+                        " 2  9.4400E-01  1.7432E-01B- ",
+                        " 6  3.6400E-02  3.1817E-02X  ",
+                    ],
+                ),
+                (
+                    "Ba-137m",
+                    &[
+                        " 1  8.9900E-01  6.6166E-01G  ",
+                        " 6  6.7000E-02  3.2194E-02X  ",
+                    ],
+                ),
+            ],
+        );
+        let data = Icrp107::open(&path).unwrap();
+        let cs137: Nuclide = "Cs-137".parse().unwrap();
+        let ba137m: Nuclide = "Ba-137m".parse().unwrap();
+
+        let mut csv = vec![];
+        data.export_spectrum_csv(ba137m, &mut csv).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "energy_kev,yield,radiation_type\n32.194,0.067,X\n661.660,0.899,G\n"
+        );
+
+        let mut csv = vec![];
+        data.export_spectrum_csv("Ra-226".parse().unwrap(), &mut csv)
+            .unwrap();
+        assert_eq!(csv, b"energy_kev,yield,radiation_type\n");
+
+        let mut csv = vec![];
+        data.export_spectra_csv(&[cs137, ba137m], &mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let rows: Vec<&str> = csv.lines().collect();
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows[0], "nuclide,energy_kev,yield,radiation_type");
+        assert_eq!(rows[1], "Cs-137,31.817,0.0364,X");
+        assert_eq!(rows[3], "Ba-137m,661.660,0.899,G");
+
+        assert!(data
+            .export_spectrum_csv("Co-60".parse().unwrap(), vec![])
+            .is_err());
+    }
+
+    #[test]
+    fn diff_gamma() {
+        let path = fixture("diff-gamma", &[NDX_CS137, NDX_BA137M]);
+        write_rad(
+            &path,
+            &[
+                (
+                    "Cs-137",
+                    &[
+                        " 2  9.4400E-01  1.7432E-01B- ",
+                        " 6  3.6400E-02  3.1817E-02X  ",
+                    ],
+                ),
+                (
+                    "Ba-137m",
+                    &[
+                        " 1  8.9900E-01  6.6166E-01G  ",
+                        " 6  6.7000E-02  3.2194E-02X  ",
+                    ],
+                ),
+            ],
+        );
+        let data = Icrp107::open(&path).unwrap();
+        let cs137: Nuclide = "Cs-137".parse().unwrap();
+        let ba137m: Nuclide = "Ba-137m".parse().unwrap();
+
+        let d = data.diff_gamma(cs137, ba137m).unwrap();
+        assert!(d.only_a.is_empty());
+        assert_eq!(d.only_b, vec![(661_660, 0.899)]);
+        assert_eq!(d.matched.len(), 1);
+        assert_eq!(d.matched[0].a, (31_817, 0.0364));
+        assert_eq!(d.matched[0].b, (32_194, 0.067));
+
+        let d = data.diff_gamma(ba137m, ba137m).unwrap();
+        assert_eq!(d.matched.len(), 2);
+        assert!(d.matched.iter().all(|m| m.yield_delta == 0.));
+
+        assert!(data.diff_gamma(cs137, "Co-60".parse().unwrap()).is_err());
+    }
+
+    #[test]
+    fn dominant_lines() {
+        let path = fixture("dominant", &[NDX_CS137, NDX_BA137M, NDX_H3]);
+        write_rad(
+            &path,
+            &[
+                (
+                    "Cs-137",
+                    &[
+                        " 2  9.4400E-01  1.7432E-01B- ",
+                        " 6  3.6400E-02  3.1817E-02X  ",
+                    ],
+                ),
+                (
+                    "Ba-137m",
+                    &[
+                        " 6  6.7000E-02  3.2194E-02X  ",
+                        " 1  8.9900E-01  6.6166E-01G  ",
+                        " 3  7.7900E-02  6.2420E-01IE ",
+                    ],
+                ),
+                ("H-3", &[" 2  1.0000E+00  5.6820E-03B- "]),
+            ],
+        );
+        let data = Icrp107::open(&path).unwrap();
+        let cs137: Nuclide = "Cs-137".parse().unwrap();
+        let ba137m: Nuclide = "Ba-137m".parse().unwrap();
+        let h3: Nuclide = "H-3".parse().unwrap();
+
+        // the 661.7 keV line of Cs-137 is emitted by Ba-137m
+        assert_eq!(data.dominant_gamma(ba137m).unwrap(), Some((661_660, 0.899)));
+        assert_eq!(data.dominant_gamma(cs137).unwrap(), Some((31_817, 0.0364)));
+        assert_eq!(
+            data.dominant_emission(cs137).unwrap(),
+            Some((RadiationType::BetaMinus, 174_320, 0.944))
+        );
+
+        assert_eq!(data.dominant_gamma(h3).unwrap(), None);
+        assert_eq!(
+            data.dominant_emission(h3).unwrap(),
+            Some((RadiationType::BetaMinus, 5_682, 1.))
+        );
+
+        assert!(matches!(
+            data.dominant_gamma("Co-60".parse().unwrap()),
+            Err(Error::InvalidNuclide(_))
+        ));
+    }
+
+    #[test]
+    fn emission_summary() {
+        let path = fixture("emission-summary", &[NDX_CO60, NDX_CS137]);
+        write_rad(
+            &path,
+            &[(
+                "Co-60",
+                &[
+                    " 2  9.9880E-01  9.5770E-02B- ",
+                    " 1  9.9850E-01  1.1732E+00G  ",
+                    " 1  9.9983E-01  1.3325E+00G  ",
+                    " 6  1.6000E-04  7.4610E-03X  ",
+                ],
+            )],
+        );
+        let data = Icrp107::open(&path).unwrap();
+
+        let summary = data.emission_summary("Co-60".parse().unwrap()).unwrap();
+        assert_eq!(summary.len(), 3);
+        let (y, e) = summary[&RadiationType::Gamma];
+        assert!((y - (0.9985 + 0.99983)).abs() < 1e-12);
+        assert!((e - (0.9985 * 1.1732 + 0.99983 * 1.3325)).abs() < 1e-12);
+        let (y, e) = summary[&RadiationType::BetaMinus];
+        assert_eq!(y, 0.9988);
+        assert!((e - 0.9988 * 0.09577).abs() < 1e-12);
+        assert!(!summary.contains_key(&RadiationType::Alpha));
+
+        assert!(data
+            .emission_summary("Cs-137".parse().unwrap())
+            .unwrap()
+            .is_empty());
+        assert!(data.emission_summary("Ba-137m".parse().unwrap()).is_err());
+    }
+
+    #[test]
+    fn chain_gamma_lines() {
+        let path = fixture("chain-gamma-cs", &[NDX_CS137, NDX_BA137M, NDX_BA137]);
+        write_rad(
+            &path,
+            &[
+                ("Cs-137", &[" 2  9.4400E-01  1.7432E-01B- "]),
+                ("Ba-137m", &[" 1  8.9900E-01  6.6166E-01G  "]),
+            ],
+        );
+        let data = Icrp107::open(&path).unwrap();
+
+        let lines = data
+            .chain_gamma_lines("Cs-137".parse().unwrap(), 0.)
+            .unwrap();
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].0, 661_660);
+        assert!((lines[0].1 - 0.944 * 0.899).abs() < 1e-12);
+        assert_eq!(
+            data.chain_gamma_lines("Ba-137m".parse().unwrap(), 0.)
+                .unwrap(),
+            vec![(661_660, 0.899)]
+        );
+
+        // Ac-226 -> Ra-226 (17%) -> Rn-222, and Th-226 and Fr-222 are not indexed
+        let path = fixture("chain-gamma-ac", &[NDX_AC226, NDX_RA226, NDX_RN222]);
+        write_rad(
+            &path,
+            &[
+                ("Ac-226", &[" 1  2.6900E-01  2.3000E-01G  "]),
+                ("Ra-226", &[" 1  3.5900E-02  1.8621E-01G  "]),
+                ("Rn-222", &[" 1  7.6000E-04  5.1000E-01G  "]),
+            ],
+        );
+        let data = Icrp107::open(&path).unwrap();
+        let ac226: Nuclide = "Ac-226".parse().unwrap();
+
+        let lines = data.chain_gamma_lines(ac226, 0.).unwrap();
+        let energies: Vec<_> = lines.iter().map(|&(e, _)| e).collect();
+        assert_eq!(energies, vec![230_000, 186_210, 510_000]);
+        assert_eq!(lines[0].1, 0.269);
+        assert!((lines[1].1 - 0.17 * 0.0359).abs() < 1e-12);
+        assert!((lines[2].1 - 0.17 * 0.00076).abs() < 1e-12);
+
+        assert_eq!(data.chain_gamma_lines(ac226, 1e-3).unwrap().len(), 2);
+        assert!(data
+            .chain_gamma_lines("Co-60".parse().unwrap(), 0.)
+            .is_err());
+    }
+
+    #[test]
+    fn air_kerma_rate_constant() {
+        let path = fixture("air-kerma-rate", &[NDX_CS137, NDX_BA137M]);
+        write_rad(
+            &path,
+            &[(
+                "Ba-137m",
+                &[
+                    " 1  8.9900E-01  6.6166E-01G  ",
+                    " 6  5.8900E-02  3.2194E-02X  ",
+                    " 3  7.7900E-02  6.2420E-01IE ",
+                ],
+            )],
+        );
+        let data = Icrp107::open(&path).unwrap();
+        let ba137m: Nuclide = "Ba-137m".parse().unwrap();
+
+        // close to the constant of NDX (Gy·m²/(Bq·s))
+        let gamma = data.air_kerma_rate_constant(ba137m).unwrap();
+        let ndx = data.ndx().unwrap()[&ba137m].air_kerma_const * 1e12 * 3600.;
+        assert!((gamma / ndx - 1.).abs() < 0.05);
+
+        assert_eq!(
+            data.air_kerma_rate_constant("Cs-137".parse().unwrap())
+                .unwrap(),
+            0.
+        );
+    }
+
+    #[test]
+    fn spectrum_of_nuclide() {
+        let path = fixture("spectrum-of", &[NDX_CS137, NDX_BA137M]);
+        write_rad(&path, &[("Ba-137m", &[" 1  8.9900E-01  6.6166E-01G  "])]);
+        let bet = "Cs-137       2\n0.0000 0.0000E+00\n0.5000 0.0000E+00\n";
+        std::fs::write(path.join("ICRP-07.BET"), bet).unwrap();
+        std::fs::write(path.join("ICRP-07.ACK"), "").unwrap();
+        std::fs::write(path.join("ICRP-07.NSF"), "").unwrap();
+        let data = Icrp107::open(&path).unwrap();
+        let cs137: Nuclide = "Cs-137".parse().unwrap();
+        let ba137m: Nuclide = "Ba-137m".parse().unwrap();
+        let co60: Nuclide = "Co-60".parse().unwrap();
+
+        let rad = data.rad_of(ba137m).unwrap();
+        assert_eq!(rad.len(), 1);
+        assert_eq!(rad[0].energy, 0.66166);
+        assert!(std::ptr::eq(rad, data.rad_of(ba137m).unwrap()));
+        assert!(data.rad_of(cs137).unwrap().is_empty());
+        assert!(matches!(data.rad_of(co60), Err(Error::InvalidNuclide(_))));
+
+        assert_eq!(data.bet_of(cs137).unwrap().len(), 2);
+        assert!(data.bet_of(ba137m).unwrap().is_empty());
+        assert!(matches!(data.bet_of(co60), Err(Error::InvalidNuclide(_))));
+
+        assert!(data.ack_of(cs137).unwrap().is_empty());
+        assert!(matches!(data.ack_of(co60), Err(Error::InvalidNuclide(_))));
+        assert!(data.nsf_of(cs137).unwrap().is_empty());
+        assert!(matches!(data.nsf_of(co60), Err(Error::InvalidNuclide(_))));
+
+        assert_eq!(data.neutron_yield(cs137).unwrap(), 0.);
+        assert_eq!(data.neutron_mean_energy(cs137).unwrap(), 0.);
+        assert!(data.neutron_yield(co60).is_err());
+    }
+
+    #[test]
+    fn preload_all() {
+        let path = fixture("preload-all", &[NDX_CS137, NDX_BA137M]);
+        write_rad(&path, &[("Ba-137m", &[" 1  8.9900E-01  6.6166E-01G  "])]);
+        let bet = "Cs-137       3\n0.0000 0.0000E+00\n0.2000 4.0000E+00\n0.5000 0.0000E+00\n";
+        std::fs::write(path.join("ICRP-07.BET"), bet).unwrap();
+        std::fs::write(path.join("ICRP-07.ACK"), "").unwrap();
+
+        // first error is kept while other files are cached
+        let data = Icrp107::open(&path).unwrap();
+        match data.preload_all() {
+            Err(Error::FileNotFound(file)) => assert_eq!(file, path.join("ICRP-07.NSF")),
+            _ => panic!("NSF should be missing"),
+        }
+        std::fs::write(path.join("ICRP-07.NSF"), "").unwrap();
+
+        let data = Icrp107::open(&path).unwrap();
+        data.preload_all().unwrap();
+
+        // cached data stays available without files
+        for file in ["NDX", "RAD", "BET", "ACK", "NSF"] {
+            std::fs::remove_file(path.join(format!("ICRP-07.{}", file))).unwrap();
+        }
+        assert_eq!(data.ndx().unwrap().len(), 2);
+        assert_eq!(data.rad().unwrap().len(), 1);
+        assert_eq!(data.bet().unwrap().len(), 1);
+        assert!(data.ack().unwrap().is_empty());
+        assert!(data.nsf().unwrap().is_empty());
+        data.preload_all().unwrap();
+    }
+
+    #[test]
+    #[ignore]
+    fn preload_all_from_data() {
+        let data = Icrp107::open(DATA_PATH).unwrap();
+        data.preload_all().unwrap();
+
+        assert!(!data.ndx().unwrap().is_empty());
+        assert!(!data.rad().unwrap().is_empty());
+        assert!(!data.bet().unwrap().is_empty());
+        assert!(!data.ack().unwrap().is_empty());
+        assert!(!data.nsf().unwrap().is_empty());
+    }
+
+    #[test]
+    fn nuclide_search() {
+        let data = Icrp107::open(fixture(
+            "nuclide-search",
+            &[NDX_RA226, NDX_BA137M, NDX_CS137, NDX_AC226],
+        ))
+        .unwrap();
+        let n = |s: &str| s.parse::<Nuclide>().unwrap();
+
+        assert_eq!(data.isotopes_of("Cs").unwrap(), vec![n("Cs-137")]);
+        assert_eq!(data.isotopes_of("Ba").unwrap(), vec![n("Ba-137m")]);
+        assert!(data.isotopes_of("Co").unwrap().is_empty());
+        assert!(matches!(
+            data.isotopes_of("Xx"),
+            Err(Error::InvalidSymbol(_))
+        ));
+
+        assert_eq!(
+            data.nuclides_in_mass_range(137, 137).unwrap(),
+            vec![n("Cs-137"), n("Ba-137m")]
+        );
+        assert_eq!(
+            data.nuclides_in_mass_range(200, 300).unwrap(),
+            vec![n("Ra-226"), n("Ac-226")]
+        );
+        assert!(data.nuclides_in_mass_range(138, 225).unwrap().is_empty());
+        assert!(data.nuclides_in_mass_range(300, 200).unwrap().is_empty());
+    }
+
+    #[test]
+    fn terminal_nuclides() {
+        let path = fixture("terminal-nuclides", &[NDX_CS137, NDX_BA137M, NDX_RA226]);
+        let data = Icrp107::open(&path).unwrap();
+
+        let terminals = data.terminal_nuclides().unwrap();
+        assert!(terminals.contains(&("Ba-137".parse().unwrap(), true)));
+        assert!(terminals.contains(&("Rn-222".parse().unwrap(), true)));
+        assert!(!terminals
+            .iter()
+            .any(|(n, _)| n == &"Ba-137m".parse().unwrap()));
+    }
+
+    #[test]
+    fn only_keeps_progeny() {
+        let path = fixture("only-progeny", &[NDX_CS137, NDX_BA137M, NDX_RA226]);
+        let cs137: Nuclide = "Cs-137".parse().unwrap();
+        let ba137m: Nuclide = "Ba-137m".parse().unwrap();
+
+        let data = Icrp107::builder()
+            .path(&path)
+            .only(&[cs137])
+            .build()
+            .unwrap();
+        let ndx = data.ndx().unwrap();
+        assert_eq!(ndx.len(), 2);
+        assert!(ndx.contains_key(&ba137m));
+
+        let terminals = data.terminal_nuclides().unwrap();
+        assert_eq!(terminals, vec![("Ba-137".parse().unwrap(), true)]);
+    }
+
+    #[test]
+    fn progeny_ref() {
+        let path = fixture("progeny-ref", &[NDX_CS137, NDX_BA137M]);
+        let data = Icrp107::open(&path).unwrap();
+        let cs137: Nuclide = "Cs-137".parse().unwrap();
+
+        let progeny = data.progeny_ref(cs137).unwrap();
+        assert_eq!(progeny.len(), 2);
+        assert_eq!(progeny[0].nuclide, "Ba-137m".parse().unwrap());
+        // borrowed from the index rather than a copy
+        assert!(std::ptr::eq(
+            progeny,
+            data.ndx().unwrap()[&cs137].progeny.as_slice()
+        ));
+
+        assert!(data.progeny_ref("Ba-137".parse().unwrap()).is_err());
+    }
+
+    #[test]
+    fn energy_partition() {
+        let path = fixture("energy-partition", &[NDX_CS137, NDX_BA137M]);
+        // triangular spectrum of endpoint 0.5 MeV with the mean energy 0.18684 MeV of NDX
+        std::fs::write(
+            path.join("ICRP-07.BET"),
+            "Cs-137       3\n0.0000 0.0000E+00\n0.0605 4.0000E+00\n0.5000 0.0000E+00\n",
+        )
+        .unwrap();
+        let data = Icrp107::open(&path).unwrap();
+        let cs137: Nuclide = "Cs-137".parse().unwrap();
+
+        // neutrinos carry the endpoint less the mean energy of beta particles
+        let partition = data.energy_partition(cs137).unwrap();
+        assert_eq!(partition.alpha, 0.);
+        assert_eq!(partition.photon, 0.);
+        assert!((partition.electron - 0.18684 / 0.5).abs() < 1e-4);
+        assert!((partition.neutrino - (0.5 - 0.18684) / 0.5).abs() < 1e-4);
+        let sum = partition.photon + partition.electron + partition.alpha + partition.neutrino;
+        assert!((sum - 1.).abs() < 1e-12);
+
+        // no beta particles, so all energy is emitted
+        let partition = data.energy_partition("Ba-137m".parse().unwrap()).unwrap();
+        assert_eq!(partition.neutrino, 0.);
+        assert!((partition.photon - 0.59807 / (0.06481 + 0.59807)).abs() < 1e-12);
+
+        assert!(matches!(
+            EnergyPartition::new(f64::NAN, 0., 0.1, 0.),
+            Err(Error::InvalidDecayEnergy(_))
+        ));
+        assert!(matches!(
+            EnergyPartition::new(0.1, 0., 0.2, 0.),
+            Err(Error::InvalidDecayEnergy(_))
+        ));
+    }
+
+    #[test]
+    fn fold_nuclides() {
+        let path = fixture(
+            "fold-nuclides",
+            &[NDX_AC226, NDX_RA226, NDX_CS137, NDX_BA137M],
+        );
+        let data = Icrp107::open(&path).unwrap();
+
+        let alpha_emitters = data
+            .fold_nuclides(0, |n, _, attr| {
+                n + attr.decay_mode.0.contains(DecayMode::Alpha) as usize
+            })
+            .unwrap();
+        assert_eq!(alpha_emitters, 2);
+
+        let nuclides = data
+            .fold_nuclides(vec![], |mut v, &nuclide, _| {
+                v.push(nuclide);
+                v
+            })
+            .unwrap();
+        assert_eq!(nuclides.len(), 4);
+        assert!(nuclides.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn builder_with_options() {
+        let path = fixture("builder", &[NDX_AC226, NDX_RA226]);
+        let ac226: Nuclide = "Ac-226".parse().unwrap();
+        let ra226: Nuclide = "Ra-226".parse().unwrap();
+
+        let data = Icrp107::open(&path).unwrap();
+        assert_eq!(data.ndx().unwrap().len(), 2);
+        assert_eq!(data.half_life(ra226).unwrap().unit, TimeUnit::Year);
+
+        let data = Icrp107::builder()
+            .path(&path)
+            .strict(true)
+            .only(&[ra226])
+            .year_convention(YearConvention::Julian)
+            .build()
+            .unwrap();
+        assert_eq!(data.ndx().unwrap().len(), 1);
+        assert!(matches!(
+            data.half_life(ac226),
+            Err(Error::InvalidNuclide(_))
+        ));
+        let half_life = data.half_life(ra226).unwrap();
+        assert_eq!(half_life.unit, TimeUnit::Year);
+        assert_eq!(half_life.value, 1600.);
+        let lambda = 2.0_f64.ln() / (1600. * 365.25 * 86_400.);
+        assert!((data.lambda(ra226).unwrap() / lambda - 1.).abs() < 1e-12);
+        let ten_half_lives = data.decay_time_for_n_half_lives(ra226, 10.).unwrap();
+        assert!((ten_half_lives.as_unit(TimeUnit::Day) / (16_000. * 365.25) - 1.).abs() < 1e-12);
+
+        let co60: Nuclide = "Co-60".parse().unwrap();
+        let builder = Icrp107::builder().path(&path).only(&[ra226, co60]);
+        assert!(builder.build().is_ok());
+        let builder = Icrp107::builder().path(&path).only(&[ra226, co60]);
+        assert!(matches!(
+            builder.strict(true).build(),
+            Err(Error::InvalidNuclide(_))
+        ));
+        assert!(matches!(
+            Icrp107::builder().build(),
+            Err(Error::InvalidFilePath)
+        ));
+    }
+
+    #[test]
+    #[ignore]
+    fn nuclides_by_specific_activity() {
+        let data = Icrp107::open(DATA_PATH).unwrap();
+        let i131: Nuclide = "I-131".parse().unwrap();
+        let cs137: Nuclide = "Cs-137".parse().unwrap();
+
+        let nuclides = data.nuclides_by_specific_activity(true).unwrap();
+        let rank = |n| nuclides.iter().position(|(nuc, _)| nuc == &n).unwrap();
+        assert!(rank(i131) < rank(cs137));
+        assert!(nuclides.windows(2).all(|w| w[0].1 >= w[1].1));
+
+        let nuclides = data.nuclides_by_specific_activity(false).unwrap();
+        assert!(nuclides.windows(2).all(|w| w[0].1 <= w[1].1));
+    }
+}
@@ -37,7 +37,7 @@ impl FixedWidth for NdxEntry {
 }
 
 #[derive(Debug, Deserialize)]
-#[serde(from = "NdxEntry")]
+#[serde(try_from = "NdxEntry")]
 pub struct Attribute {
     pub half_life: HalfLife,
     pub decay_mode: DecayModeSet,
@@ -61,8 +61,10 @@ impl FixedWidth for Attribute {
     }
 }
 
-impl From<NdxEntry> for Attribute {
-    fn from(entry: NdxEntry) -> Attribute {
+impl TryFrom<NdxEntry> for Attribute {
+    type Error = Error;
+
+    fn try_from(entry: NdxEntry) -> Result<Attribute, Error> {
         let progeny = entry
             .progeny
             .into_iter()
@@ -70,7 +72,7 @@ impl From<NdxEntry> for Attribute {
             .map(|(nuclide, branch_rate)| {
                 let decay_mode = match nuclide {
                     Nuclide::WithId(_) => {
-                        check_decay_mode(entry.nuclide, nuclide, entry.decay_mode).unwrap()
+                        check_decay_mode(entry.nuclide, nuclide, entry.decay_mode)?
                     }
                     Nuclide::FissionProducts => {
                         let mut mode_set = DecayModeSet::default();
@@ -78,15 +80,15 @@ impl From<NdxEntry> for Attribute {
                         mode_set
                     }
                 };
-                Progeny {
+                Ok(Progeny {
                     nuclide,
                     branch_rate,
                     decay_mode,
-                }
+                })
             })
-            .collect();
+            .collect::<Result<_, Error>>()?;
 
-        Attribute {
+        Ok(Attribute {
             half_life: entry.half_life,
             decay_mode: entry.decay_mode,
             progeny,
@@ -101,38 +103,29 @@ impl From<NdxEntry> for Attribute {
             amu: entry.amu,
             air_kerma_const: entry.air_kerma_const,
             air_kerma_coef: entry.air_kerma_coef,
-        }
+        })
     }
 }
 
+/// Decay modes of parent whose (Z, A) transition leads to daughter
 fn check_decay_mode(
     parent: Nuclide,
     daughter: Nuclide,
     decay_mode: DecayModeSet,
 ) -> Result<DecayModeSet, Error> {
-    let z = parent.z().unwrap();
-    let d_z = daughter.z().unwrap();
-    let a = parent.a().unwrap();
-    let d_a = daughter.a().unwrap();
-
-    let mut mode = FlagSet::default();
-
-    if z == d_z && a == d_a {
-        mode |= DecayMode::IsometricTransition & decay_mode.0;
-    } else if z == d_z + 2 && a == d_a + 4 {
-        mode |= DecayMode::Alpha & decay_mode.0;
-    } else if z + 1 == d_z && a == d_a {
-        mode |= DecayMode::BetaMinus & decay_mode.0;
-    } else if z == d_z + 1 && a == d_a {
-        mode |= (DecayMode::BetaPlus | DecayMode::ElectronCapture) & decay_mode.0;
-    }
+    let d_z = daughter.z().unwrap() as i32 - parent.z().unwrap() as i32;
+    let d_a = daughter.a().unwrap() as i32 - parent.a().unwrap() as i32;
+
+    let mode = decay_mode
+        .0
+        .into_iter()
+        .filter(|mode| mode.transition() == Some((d_z, d_a)))
+        .fold(FlagSet::default(), |set, mode| set | mode);
 
     if mode.is_empty() {
-        Err(Error::Unexpected(anyhow::anyhow!(
-            "{} -> {}: unexpected decay mode {:?}",
-            parent,
-            daughter,
-            mode
+        Err(Error::InvalidDecayMode(format!(
+            "{} -> {}: no decay mode of {:?} leads to daughter",
+            parent, daughter, decay_mode.0
         )))
     } else {
         Ok(DecayModeSet(mode))
@@ -142,7 +135,8 @@ fn check_decay_mode(
 #[cfg(test)]
 mod test {
     use super::{Attribute, NdxEntry};
-    use crate::primitive::Nuclide;
+    use crate::error::Error;
+    use crate::primitive::{DecayMode, Nuclide};
     use std::str::FromStr;
 
     #[test]
@@ -167,4 +161,29 @@ mod test {
         assert_eq!(entry.progeny[2].unwrap().0, daughter3);
         assert_eq!(attr.progeny[2].nuclide, daughter3);
     }
+
+    #[test]
+    fn test_proton_emission_in_ndx_entry() {
+        let data = "Co-53m     247ms ECP        1944      1      0     0 Fe-53       0 9.8500E-01 Fe-52       0 1.5000E-02             0        0.0             0        0.0 0.0003 0.29143 0.13271  14 140   5   99   1  52.945307 1.048E-171.048E-17
+";
+        let attr: Attribute = fixed_width::from_str(data).unwrap();
+
+        assert_eq!(attr.progeny[0].nuclide, Nuclide::from_str("Fe-53").unwrap());
+        assert_eq!(attr.progeny[0].decay_mode.0, DecayMode::ElectronCapture);
+        assert_eq!(attr.progeny[1].nuclide, Nuclide::from_str("Fe-52").unwrap());
+        assert_eq!(attr.progeny[1].decay_mode.0, DecayMode::ProtonEmission);
+    }
+
+    #[test]
+    fn test_unmatched_decay_mode_in_ndx_entry() {
+        let data = "Ac-226    29.37h B-EC       1944      1      0     0 Th-226   1108 8.3000E-01 Ra-226    822 1.7000E-01 Fr-222    361 6.0000E-05             0        0.0 0.0003 0.29143 0.13271  14 140   5   99   1 226.026097 1.048E-171.048E-17
+";
+        let entry: NdxEntry = fixed_width::from_str(data).unwrap();
+
+        assert!(matches!(
+            Attribute::try_from(entry),
+            Err(Error::InvalidDecayMode(_))
+        ));
+        assert!(fixed_width::from_str::<Attribute>(data).is_err());
+    }
 }
@@ -1,9 +1,10 @@
 use fixed_width::FieldSet;
 use std::collections::HashMap;
+use std::io::Read;
 use std::path::Path;
 use std::str::FromStr;
 
-use super::super::reader::FileReader;
+use super::super::reader::{open_or_gz, FileReader};
 use super::ndx::{Attribute, NdxEntry};
 
 use crate::error::Error;
@@ -15,8 +16,17 @@ pub struct IndexReader {
 }
 
 impl IndexReader {
+    /// Reader of NDX file, or of its gzip-compressed `.gz` if the file is absent
     pub fn new(path: &Path) -> Result<Self, Error> {
-        let reader = FileReader::new(path)?.skip_lines(1)?;
+        let (reader, path) = open_or_gz(path)?;
+        let mut index = Self::from_reader(reader)?;
+        index.reader = index.reader.named(&path);
+        Ok(index)
+    }
+
+    /// Reader of NDX content from any source, starting with its header line
+    pub fn from_reader<R: Read + 'static>(reader: R) -> Result<Self, Error> {
+        let reader = FileReader::from_reader(reader).skip_lines(1)?;
         Ok(Self { reader })
     }
 
@@ -25,9 +35,11 @@ impl IndexReader {
 
         let mut buf = String::new();
         while self.reader.read_line(&mut buf)? != 0 {
-            let row: NdxEntry =
-                fixed_width::from_str(&buf).map_err(|e| Error::Unexpected(e.into()))?;
-            ndx.insert(row.nuclide, row.into());
+            let row: NdxEntry = fixed_width::from_str(&buf)
+                .map_err(|e| self.reader.error_at(Error::Unexpected(e.into())))?;
+            let nuclide = row.nuclide;
+            let attr = row.try_into().map_err(|e| self.reader.error_at(e))?;
+            ndx.insert(nuclide, attr);
         }
 
         Ok(ndx)
@@ -43,11 +55,20 @@ impl<T> SpectrumReader<T>
 where
     T: FromStr<Err = Error>,
 {
+    /// Reader of spectrum file, or of its gzip-compressed `.gz` if the file is absent
     pub fn new(path: &Path) -> Result<Self, Error> {
-        Ok(Self {
-            reader: FileReader::new(path)?,
+        let (reader, path) = open_or_gz(path)?;
+        let mut spectrum = Self::from_reader(reader);
+        spectrum.reader = spectrum.reader.named(&path);
+        Ok(spectrum)
+    }
+
+    /// Reader of spectrum content (e.g. of RAD file) from any source
+    pub fn from_reader<R: Read + 'static>(reader: R) -> Self {
+        Self {
+            reader: FileReader::from_reader(reader),
             _marker: std::marker::PhantomData,
-        })
+        }
     }
 
     pub fn read(&mut self) -> Result<HashMap<Nuclide, Vec<T>>, Error> {
@@ -55,19 +76,12 @@ where
 
         let mut buf = String::new();
         while self.reader.read_line(&mut buf)? != 0 {
-            let nuclide: Nuclide = (buf[0..7]).parse()?;
-            let records = &buf[7..].replace('\0', " ");
-            let records = records.split_whitespace().last().ok_or_else(|| {
-                Error::Unexpected(anyhow::anyhow!("failed to get spectrum for {}", nuclide))
-            })?;
-            let records = records
-                .parse()
-                .map_err(|_| Error::InvalidInteger(records.to_string()))?;
+            let (nuclide, records) = header(&buf).map_err(|e| self.reader.error_at(e))?;
 
             let mut spectrum = vec![];
             for _ in 0..(records) {
                 self.reader.read_line(&mut buf)?;
-                spectrum.push(buf.parse()?);
+                spectrum.push(buf.parse().map_err(|e| self.reader.error_at(e))?);
             }
             inner.insert(nuclide, spectrum);
         }
@@ -76,6 +90,23 @@ where
     }
 }
 
+// Nuclide and number of records in header line of a spectrum
+fn header(buf: &str) -> Result<(Nuclide, usize), Error> {
+    let nuclide: Nuclide = buf
+        .get(0..7)
+        .ok_or_else(|| Error::InvalidNuclide(buf.trim_end().to_string()))?
+        .parse()?;
+    let records = &buf[7..].replace('\0', " ");
+    let records = records.split_whitespace().last().ok_or_else(|| {
+        Error::Unexpected(anyhow::anyhow!("failed to get spectrum for {}", nuclide))
+    })?;
+    let records = records
+        .parse()
+        .map_err(|_| Error::InvalidInteger(records.to_string()))?;
+
+    Ok((nuclide, records))
+}
+
 pub(crate) fn fields_from_fortran_format(
     fmt: &str,
     offset: usize,
@@ -150,7 +181,90 @@ pub(crate) fn fields_from_fortran_format(
 mod test {
     use fixed_width::{field, field_seq, FieldConfig};
 
-    use super::fields_from_fortran_format;
+    use super::super::spectrum::{rad::RadSpectrum, RadiationType};
+    use super::*;
+
+    const NDX: &[u8] = b"ICRP-07.NDX test fixture
+Cs-137   30.1671y B-                                 Ba-137m     0 9.4400E-01 Ba-137      0 5.6000E-02             0        0.0             0        0.0  0.0000 0.18684 0.00000   0   0   2    5   0 136.907089 2.800E-192.800E-19
+Ba-137m    2.552m IT                                 Ba-137      0 1.0000E+00             0        0.0             0        0.0             0        0.0  0.0000 0.06481 0.59807   7   3   0   10   0 136.906000 2.297E-172.297E-17
+";
+
+    #[test]
+    fn index_from_bytes() {
+        let ndx = IndexReader::from_reader(NDX).unwrap().read().unwrap();
+        assert_eq!(ndx.len(), 2);
+
+        let cs137 = &ndx[&"Cs-137".parse().unwrap()];
+        assert_eq!(cs137.half_life.to_string(), "30.1671 y");
+        assert_eq!(cs137.progeny.len(), 2);
+        assert_eq!(cs137.progeny[0].nuclide, "Ba-137m".parse().unwrap());
+        assert_eq!(cs137.progeny[0].branch_rate, 0.944);
+        assert_eq!(cs137.electron_energy, 0.18684);
+        assert_eq!(cs137.amu, 136.907089);
+
+        let ba137m = &ndx[&"Ba-137m".parse().unwrap()];
+        assert_eq!(ba137m.photon_energy, 0.59807);
+        assert_eq!(ba137m.air_kerma_const, 2.297e-17);
+    }
+
+    #[test]
+    fn spectrum_from_bytes() {
+        let rad = b"Ba-137m            1\n 1  8.9900E-01  6.6166E-01G  \n";
+        let rad = SpectrumReader::<RadSpectrum>::from_reader(&rad[..])
+            .read()
+            .unwrap();
+
+        let lines = &rad[&"Ba-137m".parse().unwrap()];
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].r#type, RadiationType::Gamma);
+        assert_eq!(lines[0].energy, 0.66166);
+    }
+
+    #[test]
+    fn error_line_numbers() {
+        let rad =
+            b"Ba-137m            2\n 1  8.9900E-01  6.6166E-01G  \n 6  1.2.3E-02  3.1817E-02X  \n";
+        let e = SpectrumReader::<RadSpectrum>::from_reader(&rad[..])
+            .read()
+            .unwrap_err();
+        match e {
+            Error::ParseAt { file, line, .. } => assert_eq!((file.as_str(), line), ("<input>", 3)),
+            _ => panic!("error should be located"),
+        }
+
+        let rad = b"Ba-137m            1\n 1  8.9900E-01  6.6166E-01G  \nBa-13x             1\n";
+        let e = SpectrumReader::<RadSpectrum>::from_reader(&rad[..])
+            .read()
+            .unwrap_err();
+        assert!(e.to_string().starts_with("<input>:3: "));
+
+        let ndx = [NDX, b"Cs-137   30.1671y B-  corrupt\n"].concat();
+        let e = IndexReader::from_reader(std::io::Cursor::new(ndx))
+            .unwrap()
+            .read()
+            .unwrap_err();
+        assert!(e.to_string().starts_with("<input>:4: "));
+
+        let path = std::env::temp_dir().join("radioactive-reader-errors");
+        std::fs::create_dir_all(&path).unwrap();
+        std::fs::write(path.join("ICRP-07.RAD"), &rad[..]).unwrap();
+        let e = SpectrumReader::<RadSpectrum>::new(&path.join("ICRP-07.RAD"))
+            .unwrap()
+            .read()
+            .unwrap_err();
+        assert!(e.to_string().starts_with("ICRP-07.RAD:3: "));
+    }
+
+    #[test]
+    fn negative_yield_rejected() {
+        let rad =
+            b"Ba-137m            2\n 1  8.9900E-01  6.6166E-01G  \n 6 -3.6400E-02  3.1817E-02X  \n";
+        let e = SpectrumReader::<RadSpectrum>::from_reader(&rad[..])
+            .read()
+            .unwrap_err();
+        assert!(e.to_string().starts_with("<input>:3: "));
+        assert!(e.to_string().contains("invalid yield"));
+    }
 
     #[test]
     fn test_fields_from_fortran_format() {
@@ -2,7 +2,7 @@ use fixed_width_derive::FixedWidth;
 use serde::Deserialize;
 use std::str::FromStr;
 
-use super::Spectrum;
+use super::{Spectrum, Yield};
 use crate::derive_from_str;
 use crate::error::Error;
 
@@ -10,14 +10,15 @@ use crate::error::Error;
 pub struct AckSpectrum {
     // yield (/nt)
     #[fixed_width(range = "0..11")]
-    r#yield: f64,
+    pub r#yield: Yield,
 
     // energy of reaidation (eV)
     #[fixed_width(range = "11..23")]
-    energy: f64,
+    pub energy: f64,
 
+    // atomic transition in shell notation, e.g. KL1L1
     #[fixed_width(range = "23..32")]
-    transition: String,
+    pub transition: String,
 }
 
 derive_from_str!(AckSpectrum);
@@ -31,3 +32,16 @@ impl From<AckSpectrum> for Spectrum {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ack_transition() {
+        let ack: AckSpectrum = "1.23400E-01 2.09200E+04   KL1L1 ".parse().unwrap();
+        assert_eq!(ack.r#yield.as_per_decay(), 0.1234);
+        assert_eq!(ack.energy, 20920.);
+        assert_eq!(ack.transition, "KL1L1");
+    }
+}
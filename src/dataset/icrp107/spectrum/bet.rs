@@ -11,11 +11,11 @@ use crate::error::Error;
 pub struct BetSpectrum {
     // energy grid point (MeV)
     #[fixed_width(range = "0..7")]
-    energy: f64,
+    pub energy: f64,
 
     // number of beta particles per MeV per nuclear transformation
     #[fixed_width(range = "7..17")]
-    number: f64,
+    pub number: f64,
 }
 
 derive_from_str!(BetSpectrum);
@@ -3,9 +3,13 @@ pub(super) mod bet;
 pub(super) mod nsf;
 pub(super) mod rad;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Copy, Clone, Deserialize, PartialEq, Eq)]
+use crate::error::Error;
+use crate::primitive::attr::{Energy, EnergyTolerance};
+use rad::RadSpectrum;
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum RadiationType {
     #[serde(rename = "G")]
     Gamma,
@@ -36,6 +40,11 @@ pub enum RadiationType {
     NeutronEmission,
 }
 
+serde_plain::derive_display_from_serialize!(RadiationType);
+serde_plain::derive_fromstr_from_deserialize!(RadiationType, |e| -> Error {
+    Error::InvalidRadiationType(e.to_string())
+});
+
 impl RadiationType {
     pub fn is_photon(self) -> bool {
         match self {
@@ -47,13 +56,274 @@ impl RadiationType {
             _ => false,
         }
     }
+
+    /// Whether radiation is an electron, positron, alpha particle or heavy ion
+    pub fn is_charged_particle(self) -> bool {
+        matches!(
+            self,
+            Self::BetaPlus
+                | Self::BetaMinus
+                | Self::DelayedBeta
+                | Self::InternalConversionElectron
+                | Self::AugerElectron
+                | Self::Alpha
+                | Self::AlphaRecoil
+                | Self::FissionFragment
+        )
+    }
+}
+
+/// Emissions per decay (/nt) of a spectrum line
+///
+/// Yields are mostly within `[0, 1]`, but may exceed 1 for multiple emissions per decay,
+/// e.g. of neutrons of spontaneous fission. Negative or non-finite yields are rejected
+/// rather than clamped, so a data line of such yield fails to parse with its location.
+#[derive(Debug, Copy, Clone, Default, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(try_from = "f64", into = "f64")]
+pub struct Yield(f64);
+
+impl Yield {
+    /// Yield of emissions per decay, e.g. 0.85
+    pub fn from_per_decay(value: f64) -> Result<Self, Error> {
+        if value.is_finite() && value >= 0. {
+            Ok(Self(value))
+        } else {
+            Err(Error::InvalidYield(value.to_string()))
+        }
+    }
+
+    /// Yield of emissions per 100 decays, e.g. 85 (%)
+    pub fn from_percent(value: f64) -> Result<Self, Error> {
+        Self::from_per_decay(value / 100.).map_err(|_| Error::InvalidYield(format!("{}%", value)))
+    }
+
+    pub fn as_per_decay(self) -> f64 {
+        self.0
+    }
+
+    pub fn as_percent(self) -> f64 {
+        self.0 * 100.
+    }
+}
+
+impl TryFrom<f64> for Yield {
+    type Error = Error;
+
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        Self::from_per_decay(value)
+    }
+}
+
+impl From<Yield> for f64 {
+    fn from(value: Yield) -> Self {
+        value.as_per_decay()
+    }
+}
+
+/// Order of spectrum lines by energy
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+// Spectrum lines sorted by energy of each line
+pub(super) fn sort_by_energy<T, F>(lines: &[T], energy: F, order: SortOrder) -> Vec<&T>
+where
+    F: Fn(&T) -> f64,
+{
+    let mut sorted: Vec<&T> = lines.iter().collect();
+    sorted.sort_by(|a, b| energy(a).total_cmp(&energy(b)));
+    if order == SortOrder::Descending {
+        sorted.reverse();
+    }
+    sorted
+}
+
+/// Emissions per decay (/nt) of lines of given type
+pub fn sum_yield(lines: &[RadSpectrum], kind: RadiationType) -> f64 {
+    lines
+        .iter()
+        .filter(|r| r.r#type == kind)
+        .map(|r| r.r#yield.as_per_decay())
+        .sum()
+}
+
+/// Energy (MeV) emitted per decay by lines of given type, i.e. sum of yield times energy
+pub fn sum_energy(lines: &[RadSpectrum], kind: RadiationType) -> f64 {
+    lines
+        .iter()
+        .filter(|r| r.r#type == kind)
+        .map(|r| r.r#yield.as_per_decay() * r.energy)
+        .sum()
+}
+
+/// Yields of spectrum lines accumulated into energy bins
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    /// Yield (/nt) of each bin
+    pub yields: Vec<f64>,
+    /// Number of lines outside of all bins
+    pub dropped: usize,
+}
+
+/// Accumulate yields of lines (energy, yield) into bins of ascending `bin_edges`
+///
+/// Bins are half-open `[lo, hi)`, so a line exactly on an inner edge falls into the
+/// upper bin, and a line on the last edge is dropped. Edges are in the unit of line
+/// energy (eV).
+pub fn histogram(lines: &[(Energy, f64)], bin_edges: &[f64]) -> Histogram {
+    let mut yields = vec![0.; bin_edges.len().saturating_sub(1)];
+    let mut dropped = 0;
+
+    for &(energy, r#yield) in lines {
+        // number of edges at or below energy
+        let i = bin_edges.partition_point(|&e| e <= energy as f64);
+        if i == 0 || i == bin_edges.len() {
+            dropped += 1;
+        } else {
+            yields[i - 1] += r#yield;
+        }
+    }
+
+    Histogram { yields, dropped }
+}
+
+/// Integrate continuous spectrum of grid points (energy, density) into bins of
+/// ascending `bin_edges`
+///
+/// Density is linearly interpolated between grid points and taken as 0 outside of
+/// the grid. Edges are in the unit of grid energy.
+pub fn integrate_bins(points: &[(f64, f64)], bin_edges: &[f64]) -> Vec<f64> {
+    bin_edges
+        .windows(2)
+        .map(|bin| {
+            points
+                .windows(2)
+                .map(|w| {
+                    let ((x0, y0), (x1, y1)) = (w[0], w[1]);
+                    let (a, b) = (x0.max(bin[0]), x1.min(bin[1]));
+                    if b > a {
+                        let y = |x: f64| y0 + (y1 - y0) * (x - x0) / (x1 - x0);
+                        0.5 * (b - a) * (y(a) + y(b))
+                    } else {
+                        0.
+                    }
+                })
+                .sum()
+        })
+        .collect()
+}
+
+/// Gaussian peaks are truncated beyond this number of standard deviations
+const BROADENING_SIGMAS: f64 = 5.;
+
+/// Fold lines (energy, yield) with a Gaussian detector response onto ascending `grid`
+///
+/// Each line is spread as a normal distribution whose FWHM is `fwhm_at(energy)`, and
+/// grid points are centers of channels extending halfway to their neighbours, so the
+/// counts of a line within the grid sum to its yield. Energies are in eV.
+pub fn broaden(lines: &[(Energy, f64)], fwhm_at: impl Fn(f64) -> f64, grid: &[f64]) -> Vec<f64> {
+    let mut counts = vec![0.; grid.len()];
+    let n = grid.len();
+
+    for &(energy, r#yield) in lines {
+        let center = energy as f64;
+        let sigma = fwhm_at(center) / (8. * 2f64.ln()).sqrt();
+        if sigma.is_nan() || sigma <= 0. {
+            continue;
+        }
+
+        let lo = grid.partition_point(|&e| e < center - BROADENING_SIGMAS * sigma);
+        let hi = grid.partition_point(|&e| e <= center + BROADENING_SIGMAS * sigma);
+        for i in lo..hi {
+            let width = (grid[(i + 1).min(n - 1)] - grid[i.saturating_sub(1)]) / 2.;
+            let z = (grid[i] - center) / sigma;
+            let density = (-0.5 * z * z).exp() / (sigma * (2. * std::f64::consts::PI).sqrt());
+            counts[i] += r#yield * density * width;
+        }
+    }
+
+    counts
+}
+
+/// Line matched between two spectra
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MatchedLine {
+    /// Line (energy, yield) of the first spectrum
+    pub a: (Energy, f64),
+    /// Line (energy, yield) of the second spectrum
+    pub b: (Energy, f64),
+    /// Yield of `b` less yield of `a`
+    pub yield_delta: f64,
+}
+
+/// Differences between lines of two spectra
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SpectrumDiff {
+    /// Lines of the first spectrum only, sorted by energy
+    pub only_a: Vec<(Energy, f64)>,
+    /// Lines of the second spectrum only, sorted by energy
+    pub only_b: Vec<(Energy, f64)>,
+    /// Matched lines sorted by energy of the first spectrum
+    pub matched: Vec<MatchedLine>,
+}
+
+/// Match lines (energy, yield) of `a` and `b` within `energy_tol` (eV)
+///
+/// Each line is matched at most once. Pairs are taken by the nearest energy first, and
+/// ties go to the lowest energy, so the result does not depend on the order of lines.
+pub fn diff(a: &[(Energy, f64)], b: &[(Energy, f64)], energy_tol: f64) -> SpectrumDiff {
+    let mut pairs = vec![];
+    for (i, &(ea, _)) in a.iter().enumerate() {
+        for (j, &(eb, _)) in b.iter().enumerate() {
+            let distance = (i64::from(ea) - i64::from(eb)).unsigned_abs();
+            if ea.approx_eq(&eb, energy_tol / 1e3) {
+                pairs.push((distance, ea, eb, i, j));
+            }
+        }
+    }
+    pairs.sort_by_key(|x| (x.0, x.1, x.2));
+
+    let mut used_a = vec![false; a.len()];
+    let mut used_b = vec![false; b.len()];
+    let mut matched = vec![];
+    for (_, _, _, i, j) in pairs {
+        if !used_a[i] && !used_b[j] {
+            used_a[i] = true;
+            used_b[j] = true;
+            matched.push(MatchedLine {
+                a: a[i],
+                b: b[j],
+                yield_delta: b[j].1 - a[i].1,
+            });
+        }
+    }
+    matched.sort_by_key(|x| (x.a.0, x.b.0));
+
+    let unmatched = |lines: &[(Energy, f64)], used: &[bool]| {
+        let mut lines: Vec<(Energy, f64)> = lines
+            .iter()
+            .zip(used)
+            .filter(|(_, &used)| !used)
+            .map(|(&l, _)| l)
+            .collect();
+        lines.sort_by(|x, y| x.0.cmp(&y.0).then(x.1.total_cmp(&y.1)));
+        lines
+    };
+
+    SpectrumDiff {
+        only_a: unmatched(a, &used_a),
+        only_b: unmatched(b, &used_b),
+        matched,
+    }
 }
 
 #[derive(Debug)]
 pub enum Spectrum {
     Radiation {
         r#type: RadiationType,
-        r#yield: f64,
+        r#yield: Yield,
         energy: f64,
     },
     Beta {
@@ -61,13 +331,228 @@ pub enum Spectrum {
         number: f64,
     },
     AugerCosterKronigElectron {
-        r#yield: f64,
+        r#yield: Yield,
         energy: f64,
         transition: String,
     },
     SpontaneousFissionNeutron {
         energy_lower: f64,
         energy_upper: f64,
-        r#yield: f64,
+        r#yield: Yield,
     },
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sort_by_energy_ascending() {
+        let lines = [(0.662, 0.85), (0.032, 0.04), (0.036, 0.01), (0.283, 0.)];
+
+        let sorted = sort_by_energy(&lines, |l| l.0, SortOrder::Ascending);
+        assert_eq!(sorted.len(), lines.len());
+        assert!(sorted.windows(2).all(|w| w[0].0 <= w[1].0));
+        assert_eq!(sorted[0].0, 0.032);
+
+        let sorted = sort_by_energy(&lines, |l| l.0, SortOrder::Descending);
+        assert!(sorted.windows(2).all(|w| w[0].0 >= w[1].0));
+        assert_eq!(sorted[0].0, 0.662);
+    }
+
+    #[test]
+    fn yield_percent() {
+        let y = Yield::from_percent(85.1).unwrap();
+        assert!((y.as_per_decay() - 0.851).abs() < 1e-12);
+        assert!((y.as_percent() - 85.1).abs() < 1e-12);
+        assert_eq!(Yield::from_per_decay(0.5).unwrap().as_percent(), 50.);
+        assert_eq!(Yield::from_per_decay(3.77).unwrap().as_per_decay(), 3.77);
+        assert_eq!(Yield::default().as_per_decay(), 0.);
+
+        assert!(matches!(
+            Yield::from_per_decay(-0.1),
+            Err(Error::InvalidYield(_))
+        ));
+        assert!(Yield::from_percent(-5.).is_err());
+        assert!(Yield::from_per_decay(f64::NAN).is_err());
+        assert!(Yield::try_from(f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn sum_by_type() {
+        let lines: Vec<RadSpectrum> = [
+            " 2  9.4400E-01  1.7432E-01B- ",
+            " 2  5.6000E-02  4.1627E-01B- ",
+            " 6  3.6400E-02  3.1817E-02X  ",
+        ]
+        .iter()
+        .map(|l| l.parse().unwrap())
+        .collect();
+
+        assert_eq!(sum_yield(&lines, RadiationType::BetaMinus), 1.);
+        let energy = 0.944 * 0.17432 + 0.056 * 0.41627;
+        assert!((sum_energy(&lines, RadiationType::BetaMinus) - energy).abs() < 1e-12);
+        assert_eq!(sum_yield(&lines, RadiationType::X), 0.0364);
+        assert_eq!(sum_yield(&lines, RadiationType::Gamma), 0.);
+        assert_eq!(sum_energy(&[], RadiationType::Gamma), 0.);
+    }
+
+    #[test]
+    fn radiation_type_codes() {
+        let codes = [
+            ("G", RadiationType::Gamma),
+            ("PG", RadiationType::PromptGamma),
+            ("DG", RadiationType::DelayedGamma),
+            ("X", RadiationType::X),
+            ("AQ", RadiationType::AnnihilationPhoton),
+            ("B+", RadiationType::BetaPlus),
+            ("B-", RadiationType::BetaMinus),
+            ("DB", RadiationType::DelayedBeta),
+            ("IE", RadiationType::InternalConversionElectron),
+            ("AE", RadiationType::AugerElectron),
+            ("A", RadiationType::Alpha),
+            ("AR", RadiationType::AlphaRecoil),
+            ("FF", RadiationType::FissionFragment),
+            ("N", RadiationType::NeutronEmission),
+        ];
+        for (code, r#type) in codes {
+            assert_eq!(code.parse::<RadiationType>().unwrap(), r#type);
+            assert_eq!(r#type.to_string(), code);
+            assert!(!(r#type.is_photon() && r#type.is_charged_particle()));
+        }
+
+        assert!(matches!(
+            "Q".parse::<RadiationType>(),
+            Err(Error::InvalidRadiationType(_))
+        ));
+    }
+
+    #[test]
+    fn radiation_type_classes() {
+        assert!(RadiationType::Gamma.is_photon());
+        assert!(RadiationType::X.is_photon());
+        assert!(!RadiationType::Alpha.is_photon());
+        assert!(!RadiationType::BetaMinus.is_photon());
+
+        assert!(RadiationType::Alpha.is_charged_particle());
+        assert!(RadiationType::BetaPlus.is_charged_particle());
+        assert!(RadiationType::AugerElectron.is_charged_particle());
+        assert!(!RadiationType::Gamma.is_charged_particle());
+        assert!(!RadiationType::NeutronEmission.is_charged_particle());
+    }
+
+    #[test]
+    fn histogram_of_lines() {
+        let lines = [(32_000, 0.06), (662_000, 0.85)];
+
+        let hist = histogram(&lines, &[0., 100e3, 500e3, 1e6]);
+        assert_eq!(hist.yields, vec![0.06, 0., 0.85]);
+        assert_eq!(hist.dropped, 0);
+
+        let hist = histogram(&lines, &[100e3, 500e3]);
+        assert_eq!(hist.yields, vec![0.]);
+        assert_eq!(hist.dropped, 2);
+
+        assert_eq!(histogram(&lines, &[0.]).dropped, 2);
+    }
+
+    #[test]
+    fn histogram_on_bin_edges() {
+        let lines = [(100_000, 0.1), (200_000, 0.2), (300_000, 0.3)];
+
+        let hist = histogram(&lines, &[100e3, 200e3, 300e3]);
+        assert_eq!(hist.yields, vec![0.1, 0.2]);
+        assert_eq!(hist.dropped, 1);
+    }
+
+    #[test]
+    fn diff_of_lines() {
+        let a = [(661_660, 0.851), (32_194, 0.0364), (31_817, 0.0199)];
+        let b = [(31_817, 0.0199), (661_657, 0.899), (283_500, 0.0006)];
+
+        let d = diff(&a, &b, 1000.);
+        assert_eq!(d.only_a, vec![(32_194, 0.0364)]);
+        assert_eq!(d.only_b, vec![(283_500, 0.0006)]);
+        assert_eq!(d.matched.len(), 2);
+        assert_eq!(d.matched[0].a, (31_817, 0.0199));
+        assert_eq!(d.matched[0].yield_delta, 0.);
+        assert_eq!(d.matched[1].a, (661_660, 0.851));
+        assert_eq!(d.matched[1].b, (661_657, 0.899));
+        assert!((d.matched[1].yield_delta - 0.048).abs() < 1e-12);
+
+        let reversed: Vec<_> = a.iter().rev().copied().collect();
+        assert_eq!(diff(&reversed, &b, 1000.), d);
+
+        let d = diff(&a, &[], 1000.);
+        assert_eq!(d.only_a.len(), 3);
+        assert!(d.only_b.is_empty() && d.matched.is_empty());
+    }
+
+    #[test]
+    fn diff_matches_nearest_then_lowest() {
+        // both lines of A are 100 eV off the line of B, which goes to the lower one
+        let d = diff(&[(31_800, 0.1), (32_000, 0.2)], &[(31_900, 0.3)], 1000.);
+        assert_eq!(d.matched.len(), 1);
+        assert_eq!(d.matched[0].a, (31_800, 0.1));
+        assert_eq!(d.only_a, vec![(32_000, 0.2)]);
+
+        let d = diff(&[(32_000, 0.2)], &[(31_900, 0.3), (32_100, 0.4)], 1000.);
+        assert_eq!(d.matched[0].b, (31_900, 0.3));
+        assert_eq!(d.only_b, vec![(32_100, 0.4)]);
+
+        let d = diff(&[(32_000, 0.2)], &[(32_100, 0.4)], 50.);
+        assert!(d.matched.is_empty());
+    }
+
+    #[test]
+    fn broaden_single_line() {
+        let grid: Vec<f64> = (0..=20_000).map(|i| 100. * i as f64).collect();
+        let hpge = |e: f64| 1000. + 0.5 * e.sqrt();
+
+        let counts = broaden(&[(661_660, 0.85)], hpge, &grid);
+        assert_eq!(counts.len(), grid.len());
+        assert!((counts.iter().sum::<f64>() - 0.85).abs() < 1e-5);
+
+        // peak at the channel of the line, and nothing beyond the truncated tails
+        let peak = (0..counts.len())
+            .max_by(|&a, &b| counts[a].total_cmp(&counts[b]))
+            .unwrap();
+        assert_eq!(grid[peak], 661_700.);
+        assert_eq!(counts[6000], 0.);
+
+        let counts = broaden(&[(3_000_000, 0.85)], hpge, &grid);
+        assert!(counts.iter().all(|&c| c == 0.));
+        assert!(broaden(&[(661_660, 0.85)], hpge, &[]).is_empty());
+    }
+
+    #[test]
+    fn broaden_close_lines() {
+        let grid: Vec<f64> = (0..=10_000).map(|i| 200. * i as f64).collect();
+        let lines = [(661_660, 0.5), (670_000, 0.5)];
+        let maxima = |counts: &[f64]| {
+            counts
+                .windows(3)
+                .filter(|w| w[1] > w[0] && w[1] >= w[2])
+                .count()
+        };
+
+        // 7% resolution of NaI merges lines 8 keV apart into a single peak
+        let nai = broaden(&lines, |e| 0.07 * e, &grid);
+        assert_eq!(maxima(&nai), 1);
+        assert!((nai.iter().sum::<f64>() - 1.).abs() < 1e-5);
+
+        let hpge = broaden(&lines, |_| 1500., &grid);
+        assert_eq!(maxima(&hpge), 2);
+    }
+
+    #[test]
+    fn integrate_beta_points() {
+        // triangular spectrum of unit area
+        let points = [(0., 0.), (1., 2.), (2., 0.)];
+
+        let bins = integrate_bins(&points, &[0., 0.5, 1.5, 3.]);
+        assert_eq!(bins, vec![0.25, 1.5, 0.25]);
+        assert_eq!(integrate_bins(&points, &[2., 3.]), vec![0.]);
+        assert!(integrate_bins(&points, &[1.]).is_empty());
+    }
+}
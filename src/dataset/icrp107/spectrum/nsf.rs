@@ -2,7 +2,7 @@ use fixed_width_derive::FixedWidth;
 use serde::Deserialize;
 use std::str::FromStr;
 
-use super::Spectrum;
+use super::{Spectrum, Yield};
 use crate::derive_from_str;
 use crate::error::Error;
 
@@ -10,15 +10,15 @@ use crate::error::Error;
 pub struct NsfSpectrum {
     // lower energy (MeV)
     #[fixed_width(range = "0..8")]
-    energy_lower: f64,
+    pub energy_lower: f64,
 
     // upper energy (MeV)
     #[fixed_width(range = "8..17")]
-    energy_upper: f64,
+    pub energy_upper: f64,
 
     // yield (/nt)
     #[fixed_width(range = "17..29")]
-    r#yield: f64,
+    pub r#yield: Yield,
 }
 
 derive_from_str!(NsfSpectrum);
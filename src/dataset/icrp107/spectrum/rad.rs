@@ -2,10 +2,16 @@ use fixed_width_derive::FixedWidth;
 use serde::Deserialize;
 use std::str::FromStr;
 
-use super::{RadiationType, Spectrum};
+use super::{RadiationType, Spectrum, Yield};
 use crate::derive_from_str;
 use crate::error::Error;
 
+/// Discrete radiation of RAD file
+///
+/// A RAD record carries only the radiation type (columns 0..2 hold its numeric code, the
+/// same as `type`), yield and energy. ICRP-07 does not tabulate the originating
+/// transition or level of a line, which is only given for Auger and Coster-Kronig
+/// electrons in ACK file (see `AckSpectrum::transition`).
 #[derive(Debug, FixedWidth, Deserialize)]
 pub struct RadSpectrum {
     #[fixed_width(range = "26..29")]
@@ -13,7 +19,7 @@ pub struct RadSpectrum {
 
     // yield (/nt)
     #[fixed_width(range = "2..14")]
-    pub r#yield: f64,
+    pub r#yield: Yield,
 
     // energy of reaidation (MeV)
     #[fixed_width(range = "14..26")]
@@ -0,0 +1,136 @@
+use std::fmt;
+
+use super::spectrum::{rad::RadSpectrum, RadiationType};
+use super::{ndx, Icrp107};
+use crate::error::Error;
+use crate::primitive::Nuclide;
+
+/// Relative difference of emitted energies allowed between RAD and NDX
+const RELATIVE_TOLERANCE: f64 = 0.01;
+
+/// Absolute difference (MeV) always allowed, as NDX energies are given to 5 decimals
+const ABSOLUTE_TOLERANCE_MEV: f64 = 1e-5;
+
+/// Total recorded for each nuclide in NDX
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum NdxQuantity {
+    /// Energy (MeV/nt) emitted as alpha particles
+    AlphaEnergy,
+    /// Energy (MeV/nt) emitted as beta particles and mono-energetic electrons
+    ElectronEnergy,
+    /// Energy (MeV/nt) emitted as photons
+    PhotonEnergy,
+}
+
+impl fmt::Display for NdxQuantity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::AlphaEnergy => write!(f, "alpha energy"),
+            Self::ElectronEnergy => write!(f, "electron energy"),
+            Self::PhotonEnergy => write!(f, "photon energy"),
+        }
+    }
+}
+
+/// Discrepancy between a total of NDX and the sum over spectrum lines of a nuclide
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationWarning {
+    pub nuclide: Nuclide,
+    pub quantity: NdxQuantity,
+    /// Total recorded in NDX
+    pub expected: f64,
+    /// Sum over RAD lines
+    pub actual: f64,
+    /// `(actual - expected) / expected`, infinite if nothing is expected
+    pub relative_error: f64,
+}
+
+impl fmt::Display for ValidationWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} is {} but {} expected ({:+.2}%)",
+            self.nuclide,
+            self.quantity,
+            self.actual,
+            self.expected,
+            self.relative_error * 100.
+        )
+    }
+}
+
+impl Icrp107 {
+    /// Cross-check emitted energies of RAD lines against totals of NDX for all nuclides
+    ///
+    /// A warning is given for each total differing by more than 1%, e.g. of a corrupted
+    /// or truncated RAD file. Nuclides absent from RAD emit nothing. Warnings are sorted
+    /// by nuclide and quantity, and it is an error only if NDX or RAD cannot be read.
+    pub fn validate(&self) -> Result<Vec<ValidationWarning>, Error> {
+        let rad = self.rad()?;
+        let mut warnings = vec![];
+
+        for (&nuclide, attr) in self.ndx()? {
+            let spectrum = rad.get(&nuclide).map_or(&[][..], |s| s.as_slice());
+            warnings.extend(energy_warnings(nuclide, attr, spectrum));
+        }
+
+        warnings.sort_by_key(|w| (w.nuclide, w.quantity));
+        Ok(warnings)
+    }
+}
+
+fn energy_warnings(
+    nuclide: Nuclide,
+    attr: &ndx::Attribute,
+    spectrum: &[RadSpectrum],
+) -> Vec<ValidationWarning> {
+    let energy = |f: fn(RadiationType) -> bool| -> f64 {
+        spectrum
+            .iter()
+            .filter(|r| f(r.r#type))
+            .map(|r| r.r#yield.as_per_decay() * r.energy)
+            .sum()
+    };
+
+    [
+        (
+            NdxQuantity::AlphaEnergy,
+            attr.alpha_energy,
+            energy(|t| t == RadiationType::Alpha),
+        ),
+        (
+            NdxQuantity::ElectronEnergy,
+            attr.electron_energy,
+            energy(is_electron),
+        ),
+        (
+            NdxQuantity::PhotonEnergy,
+            attr.photon_energy,
+            energy(RadiationType::is_photon),
+        ),
+    ]
+    .into_iter()
+    .filter(|&(_, expected, actual)| {
+        let diff = (actual - expected).abs();
+        diff > ABSOLUTE_TOLERANCE_MEV && diff > RELATIVE_TOLERANCE * expected
+    })
+    .map(|(quantity, expected, actual)| ValidationWarning {
+        nuclide,
+        quantity,
+        expected,
+        actual,
+        relative_error: (actual - expected) / expected,
+    })
+    .collect()
+}
+
+fn is_electron(r#type: RadiationType) -> bool {
+    matches!(
+        r#type,
+        RadiationType::BetaMinus
+            | RadiationType::BetaPlus
+            | RadiationType::DelayedBeta
+            | RadiationType::InternalConversionElectron
+            | RadiationType::AugerElectron
+    )
+}
@@ -3,6 +3,6 @@ pub mod nist;
 pub mod radtoolbox;
 mod reader;
 
-pub use icrp107::Icrp107;
+pub use icrp107::{EnergyPartition, Icrp107, Icrp107Builder, NdxQuantity, ValidationWarning};
 pub use nist::NistMassAttenCoef;
 pub use radtoolbox::RadToolbox3;
@@ -14,10 +14,6 @@ use crate::primitive::notation::Material;
 use crate::primitive::Symbol;
 use reader::{MassAttenCoefReader, MaterialConstantReader};
 
-static MATEAIAL_CONSTANTS: OnceCell<BTreeMap<Symbol, MaterialConstant>> = OnceCell::new();
-static ATTENUATION_COEF: OnceCell<BTreeMap<Symbol, BTreeMap<Energy, MassAttenCoef>>> =
-    OnceCell::new();
-
 #[derive(Deserialize, FixedWidth)]
 pub struct MaterialConstantRecord {
     #[fixed_width(range = "4..6")]
@@ -88,6 +84,8 @@ impl From<MassAttenCoefRecord> for MassAttenCoef {
 
 pub struct NistMassAttenCoef {
     path: PathBuf,
+    material_constants: OnceCell<BTreeMap<Symbol, MaterialConstant>>,
+    mass_atten_coef: OnceCell<BTreeMap<Symbol, BTreeMap<Energy, MassAttenCoef>>>,
 }
 
 impl NistMassAttenCoef {
@@ -95,14 +93,18 @@ impl NistMassAttenCoef {
         let path = path.as_ref().to_path_buf();
 
         if path.is_dir() {
-            Ok(Self { path })
+            Ok(Self {
+                path,
+                material_constants: OnceCell::new(),
+                mass_atten_coef: OnceCell::new(),
+            })
         } else {
             Err(Error::Unexpected(anyhow::anyhow!("Invalid data path")))
         }
     }
 
     pub fn material_constants(&self) -> Result<&BTreeMap<Symbol, MaterialConstant>, Error> {
-        MATEAIAL_CONSTANTS.get_or_try_init(|| {
+        self.material_constants.get_or_try_init(|| {
             MaterialConstantReader::new(&self.path.join("material_constants"))?.read()
         })
     }
@@ -110,7 +112,7 @@ impl NistMassAttenCoef {
     pub fn mass_atten_coef(
         &self,
     ) -> Result<&BTreeMap<Symbol, BTreeMap<Energy, MassAttenCoef>>, Error> {
-        ATTENUATION_COEF.get_or_try_init(|| {
+        self.mass_atten_coef.get_or_try_init(|| {
             let mut content = BTreeMap::new();
 
             for z in 1..=92 {
@@ -118,7 +120,7 @@ impl NistMassAttenCoef {
                 let value = MassAttenCoefReader::new(&self.path, z)?
                     .read()?
                     .into_iter()
-                    .map(|r| ((r.energy * 1_000_000f64) as u32, r.into()))
+                    .map(|r| ((r.energy * 1e6).round() as Energy, r.into()))
                     .collect();
 
                 content.insert(symbol, value);
@@ -139,6 +141,7 @@ impl AtomicMass for NistMassAttenCoef {
 }
 
 impl MassAttenuationCoefficient for NistMassAttenCoef {
+    /// Coefficients between tabulated energies are interpolated log-log
     fn mass_attenuation_coefficient(
         &self,
         material: &Material,
@@ -147,16 +150,104 @@ impl MassAttenuationCoefficient for NistMassAttenCoef {
         let mut coef = 0f64;
 
         for (symbol, wf) in material.weight_fraction() {
-            coef += wf
-                * self
-                    .mass_atten_coef()?
-                    .get(symbol)
-                    .unwrap()
-                    .get(&energy)
-                    .map(|r| r.mu_over_rho)
-                    .ok_or(Error::InvalidEnergy(energy))?;
+            let table = self
+                .mass_atten_coef()?
+                .get(symbol)
+                .ok_or_else(|| Error::InvalidSymbol(symbol.to_string()))?;
+            coef += wf * mu_over_rho(table, energy).ok_or(Error::InvalidEnergy(energy))?;
         }
 
         Ok(coef)
     }
 }
+
+// Mass attenuation coefficient (cm2/g) at energy, None if out of the tabulated range
+fn mu_over_rho(table: &BTreeMap<Energy, MassAttenCoef>, energy: Energy) -> Option<f64> {
+    if let Some(r) = table.get(&energy) {
+        return Some(r.mu_over_rho);
+    }
+
+    let (&e0, r0) = table.range(..energy).next_back()?;
+    let (&e1, r1) = table.range(energy..).next()?;
+    let f = (energy as f64 / e0 as f64).ln() / (e1 as f64 / e0 as f64).ln();
+    Some((r0.mu_over_rho.ln() + f * (r1.mu_over_rho / r0.mu_over_rho).ln()).exp())
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::primitive::attr::AttenuationData;
+    use crate::primitive::notation::MaterialBuilder;
+
+    // Dataset directory with material constants of hydrogen, with given Z/A
+    fn fixture(name: &str, z_over_a: f64) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("radioactive-nist-{}", name));
+        std::fs::create_dir_all(&path).unwrap();
+        std::fs::write(
+            path.join("material_constants"),
+            format!(
+                "material constants test fixture\n\n  1 H {:>24}{:>10.5}{:>10.1}{:>10.3E}\n",
+                "", z_over_a, 19.2, 8.375e-5
+            ),
+        )
+        .unwrap();
+        path
+    }
+
+    #[test]
+    fn instances_cache_separately() {
+        let a = NistMassAttenCoef::open(fixture("cache-a", 0.99212)).unwrap();
+        let b = NistMassAttenCoef::open(fixture("cache-b", 0.5)).unwrap();
+
+        assert!((a.atomic_mass(Symbol::H).unwrap() - 1. / 0.99212).abs() < 1e-9);
+        assert!((b.atomic_mass(Symbol::H).unwrap() - 2.).abs() < 1e-9);
+        assert!((a.atomic_mass(Symbol::H).unwrap() - 1. / 0.99212).abs() < 1e-9);
+        assert!(a.atomic_mass(Symbol::He).is_err());
+    }
+
+    #[test]
+    fn mass_attenuation_between_grid_points() {
+        let path = fixture("interpolation", 0.99212);
+        let coef = |mu_over_rho| MassAttenCoef {
+            mu_over_rho,
+            mu_en_over_rho: 0.,
+        };
+        let table = BTreeMap::from([(600_000, coef(0.1195)), (800_000, coef(0.1050))]);
+        let data = NistMassAttenCoef {
+            path: path.clone(),
+            material_constants: OnceCell::new(),
+            mass_atten_coef: OnceCell::with_value(BTreeMap::from([(Symbol::H, table)])),
+        };
+        let hydrogen = MaterialBuilder::new(Arc::new(NistMassAttenCoef::open(&path).unwrap()))
+            .formula("H")
+            .unwrap()
+            .weight(1.)
+            .density(8.375e-5)
+            .build()
+            .unwrap();
+
+        let f = (0.661657f64 / 0.6).ln() / (0.8f64 / 0.6).ln();
+        let expected = (0.1195f64.ln() + f * (0.1050f64 / 0.1195).ln()).exp();
+        let mu = data.mass_attenuation(&hydrogen, 0.661657).unwrap();
+        assert!((mu - expected).abs() < 1e-12);
+        assert!(mu < 0.1195 && mu > 0.1050);
+
+        assert_eq!(data.mass_attenuation(&hydrogen, 0.6), Some(0.1195));
+        assert!(matches!(
+            data.mass_attenuation_coefficient(&hydrogen, 1_000_000),
+            Err(Error::InvalidEnergy(1_000_000))
+        ));
+
+        let data = NistMassAttenCoef {
+            path,
+            material_constants: OnceCell::new(),
+            mass_atten_coef: OnceCell::with_value(BTreeMap::new()),
+        };
+        assert!(matches!(
+            data.mass_attenuation_coefficient(&hydrogen, 600_000),
+            Err(Error::InvalidSymbol(_))
+        ));
+    }
+}
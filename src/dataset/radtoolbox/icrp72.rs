@@ -103,7 +103,8 @@ impl DcfInhalation for Icrp72 {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::primitive::dose_coefficient::PulmonaryAbsorptionType;
+    use crate::primitive::attr::IntakeDose;
+    use crate::primitive::dose_coefficient::{Pathway, PulmonaryAbsorptionType};
 
     const DATA_PATH: &str = "data/RadToolbox3/icrp72.mdb";
 
@@ -239,4 +240,20 @@ mod test {
             ]
         );
     }
+
+    #[test]
+    #[ignore]
+    fn effective_dose_cs137_ingestion() {
+        let db = Icrp72::open(DATA_PATH).unwrap();
+        let dcf = db
+            .dose_coefficient(
+                "Cs-137".parse().unwrap(),
+                Pathway::Ingestion,
+                AgeGroup::Adult,
+                Organ::EffectiveDose,
+            )
+            .unwrap();
+
+        assert_eq!(dcf, 1.3e-8);
+    }
 }
@@ -1,26 +1,118 @@
+use flate2::read::GzDecoder;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::path::Path;
+use std::io::{BufRead, BufReader, ErrorKind, Read};
+use std::path::{Path, PathBuf};
 
 use crate::error::Error;
 
-pub struct FileReader(BufReader<File>);
+pub fn open(path: &Path) -> Result<File, Error> {
+    match File::open(path) {
+        Ok(file) => Ok(file),
+        Err(e) if e.kind() == ErrorKind::NotFound => Err(Error::FileNotFound(path.into())),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Open file of path, or decompress `<path>.gz` if the file is absent, with the path opened
+///
+/// It is [`Error::FileNotFound`] of path if neither exists.
+pub fn open_or_gz(path: &Path) -> Result<(Box<dyn Read>, PathBuf), Error> {
+    match open(path) {
+        Ok(file) => Ok((Box::new(file), path.to_path_buf())),
+        Err(Error::FileNotFound(_)) => {
+            let mut gz = path.as_os_str().to_owned();
+            gz.push(".gz");
+            let gz = PathBuf::from(gz);
+            match open(&gz) {
+                Ok(file) => Ok((Box::new(GzDecoder::new(file)), gz)),
+                Err(Error::FileNotFound(_)) => Err(Error::FileNotFound(path.into())),
+                Err(e) => Err(e),
+            }
+        }
+        Err(e) => Err(e),
+    }
+}
+
+pub struct FileReader {
+    inner: Box<dyn BufRead>,
+    // name of source in error messages, e.g. file name
+    name: String,
+    // number of lines read
+    line: usize,
+}
 
 impl FileReader {
     pub fn new(path: &Path) -> Result<Self, Error> {
-        Ok(Self(BufReader::new(File::open(path)?)))
+        Ok(Self::from_reader(open(path)?).named(path))
+    }
+
+    /// Reader of any source, e.g. an embedded or decompressed file
+    pub fn from_reader<R: Read + 'static>(reader: R) -> Self {
+        Self {
+            inner: Box::new(BufReader::new(reader)),
+            name: "<input>".to_string(),
+            line: 0,
+        }
+    }
+
+    /// Name source by file name of path in error messages
+    pub fn named(mut self, path: &Path) -> Self {
+        self.name = path
+            .file_name()
+            .map_or(path.to_string_lossy(), |name| name.to_string_lossy())
+            .to_string();
+        self
     }
 
     pub fn skip_lines(mut self, n: usize) -> Result<Self, Error> {
         let mut buf = vec![];
         for _ in 0..n {
-            self.0.read_until(b'\n', &mut buf)?;
+            if self.inner.read_until(b'\n', &mut buf)? != 0 {
+                self.line += 1;
+            }
         }
         Ok(self)
     }
 
     pub fn read_line(&mut self, buf: &mut String) -> Result<usize, Error> {
         buf.clear();
-        self.0.read_line(buf).map_err(std::convert::Into::into)
+        let n = self.inner.read_line(buf)?;
+        if n != 0 {
+            self.line += 1;
+        }
+        Ok(n)
+    }
+
+    /// Error located at the last line read
+    pub fn error_at(&self, error: Error) -> Error {
+        Error::ParseAt {
+            file: self.name.clone(),
+            line: self.line,
+            source: Box::new(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn line_numbers() {
+        let mut reader = FileReader::from_reader(&b"header\nfirst\nsecond"[..])
+            .skip_lines(1)
+            .unwrap();
+        let mut buf = String::new();
+        reader.read_line(&mut buf).unwrap();
+        reader.read_line(&mut buf).unwrap();
+        assert_eq!(buf, "second");
+        assert_eq!(reader.read_line(&mut buf).unwrap(), 0);
+
+        let e = reader.error_at(Error::InvalidFloat("1.2.3".to_string()));
+        assert_eq!(e.to_string(), "<input>:3: invalid float number: 1.2.3");
+
+        let reader = FileReader::from_reader(&b""[..]).named(Path::new("/data/ICRP-07.RAD"));
+        let e = reader.error_at(Error::InvalidInteger("x".to_string()));
+        assert_eq!(e.to_string(), "ICRP-07.RAD:0: invalid integer: x");
     }
 }
@@ -0,0 +1,343 @@
+use std::collections::HashMap;
+
+use super::tree::{ChainTree, ChainTreeNode};
+use super::DecaySeries;
+use crate::error::Error;
+use crate::primitive::attr::{DecayConstant, IntakeDose, NuclideHalfLife, NuclideProgeny};
+use crate::primitive::{AgeGroup, Nuclide, Organ, Pathway};
+
+pub trait ChainDose {
+    /// Share of committed effective dose of each chain member for intake of root
+    ///
+    /// Each member is assumed to be taken in with the activity of root scaled by its
+    /// cumulative yield, i.e. the chain is in secular equilibrium at intake. Members
+    /// without a dose coefficient (e.g. stable end products) are omitted, and fractions
+    /// of the remaining members sum to 1.
+    fn dose_contribution_by_member(
+        &self,
+        root: Nuclide,
+        activity_bq: f64,
+        pathway: Pathway,
+        age_group: AgeGroup,
+    ) -> Result<HashMap<Nuclide, f64>, Error>;
+
+    /// Dose (Sv) of each chain member integrated over period (s) after intake of root
+    ///
+    /// Biokinetics is not modelled: the committed dose of each member is taken as
+    /// delivered over its physical decay, so the fraction `1 - exp(-λt)` of it is
+    /// received within the period. An infinite period gives the full committed dose, while
+    /// a negative or NaN period is an error.
+    /// Members are taken in as for [`ChainDose::dose_contribution_by_member`].
+    fn dose_by_member_over_period(
+        &self,
+        root: Nuclide,
+        activity_bq: f64,
+        pathway: Pathway,
+        age_group: AgeGroup,
+        period: f64,
+    ) -> Result<HashMap<Nuclide, f64>, Error>;
+
+    /// Committed effective dose (Sv) of intake (Bq) of root and its radioactive progeny
+    ///
+    /// Progeny are walked along the tree of [`ChainTree::build_tree`], each taken in
+    /// with the activity of root scaled by the branch rates along its path. Stable
+    /// daughters and those without a tabulated coefficient contribute zero, while root
+    /// must have a coefficient.
+    fn committed_effective(
+        &self,
+        root: Nuclide,
+        pathway: Pathway,
+        age_group: AgeGroup,
+        intake_bq: f64,
+    ) -> Result<f64, Error>;
+}
+
+impl<T> ChainDose for T
+where
+    T: IntakeDose + NuclideProgeny + NuclideHalfLife,
+{
+    fn dose_contribution_by_member(
+        &self,
+        root: Nuclide,
+        activity_bq: f64,
+        pathway: Pathway,
+        age_group: AgeGroup,
+    ) -> Result<HashMap<Nuclide, f64>, Error> {
+        let mut doses = HashMap::new();
+        for (nuclide, y) in self.cumulative_yields(root)? {
+            match self.intake_dose_coefficient(nuclide, pathway, age_group) {
+                Ok(coef) => {
+                    doses.insert(nuclide, activity_bq * y * coef);
+                }
+                Err(Error::NoDoseCoefficient(..)) => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        let tot: f64 = doses.values().sum();
+        if tot <= 0. {
+            return Err(Error::NoDoseCoefficient(
+                root,
+                pathway,
+                Organ::EffectiveDose,
+            ));
+        }
+
+        for dose in doses.values_mut() {
+            *dose /= tot;
+        }
+
+        Ok(doses)
+    }
+
+    fn dose_by_member_over_period(
+        &self,
+        root: Nuclide,
+        activity_bq: f64,
+        pathway: Pathway,
+        age_group: AgeGroup,
+        period: f64,
+    ) -> Result<HashMap<Nuclide, f64>, Error> {
+        if period.is_nan() || period < 0. {
+            return Err(Error::InvalidTime(format!("period of {} s", period)));
+        }
+
+        let mut doses = HashMap::new();
+        for (nuclide, y) in self.cumulative_yields(root)? {
+            match self.intake_dose_coefficient(nuclide, pathway, age_group) {
+                Ok(coef) => {
+                    let fraction = delivered_fraction(self.lambda(nuclide)?, period);
+                    doses.insert(nuclide, activity_bq * y * coef * fraction);
+                }
+                Err(Error::NoDoseCoefficient(..)) => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(doses)
+    }
+
+    fn committed_effective(
+        &self,
+        root: Nuclide,
+        pathway: Pathway,
+        age_group: AgeGroup,
+        intake_bq: f64,
+    ) -> Result<f64, Error> {
+        let mut dose = intake_bq * self.intake_dose_coefficient(root, pathway, age_group)?;
+
+        let tree = self.build_tree(root)?;
+        let mut stack: Vec<(&ChainTreeNode, f64)> =
+            tree.children.iter().map(|c| (c, c.branch_rate)).collect();
+        while let Some((node, y)) = stack.pop() {
+            if node.back_reference {
+                continue;
+            }
+            stack.extend(node.children.iter().map(|c| (c, y * c.branch_rate)));
+
+            let radioactive = node.half_life.is_some_and(|hl| !hl.is_stable());
+            if !radioactive {
+                continue;
+            }
+            match self.intake_dose_coefficient(node.nuclide, pathway, age_group) {
+                Ok(coef) => dose += intake_bq * y * coef,
+                Err(Error::NoDoseCoefficient(..)) => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(dose)
+    }
+}
+
+// Fraction of committed dose delivered within period (non-negative, may be infinite), by
+// physical decay only
+fn delivered_fraction(lambda: f64, period: f64) -> f64 {
+    if period.is_infinite() {
+        1.
+    } else {
+        1. - (-lambda * period).exp()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::primitive::attr::{DcfIngestion, DcfInhalation};
+    use crate::primitive::{DcfValue, DecayModeSet, HalfLife, Progeny};
+
+    struct TestData;
+
+    impl NuclideHalfLife for TestData {
+        fn half_life(&self, nuclide: Nuclide) -> Result<HalfLife, Error> {
+            if nuclide == "Sr-90".parse().unwrap() {
+                "28.79y".parse()
+            } else if nuclide == "Y-90".parse().unwrap() {
+                "64.05h".parse()
+            } else {
+                Err(Error::InvalidNuclide(nuclide.to_string()))
+            }
+        }
+    }
+
+    impl NuclideProgeny for TestData {
+        fn progeny(&self, nuclide: Nuclide) -> Result<Vec<Progeny>, Error> {
+            let progeny = |d: &str| Progeny {
+                nuclide: d.parse().unwrap(),
+                branch_rate: 1.,
+                decay_mode: DecayModeSet::default(),
+            };
+
+            if nuclide == "Sr-90".parse().unwrap() {
+                Ok(vec![progeny("Y-90")])
+            } else if nuclide == "Y-90".parse().unwrap() {
+                Ok(vec![progeny("Zr-90")])
+            } else {
+                Err(Error::InvalidNuclide(nuclide.to_string()))
+            }
+        }
+    }
+
+    impl DcfIngestion for TestData {
+        fn dcf_ingestion(
+            &self,
+            nuclide: Nuclide,
+            age_group: AgeGroup,
+            _organ: Organ,
+        ) -> Result<Vec<DcfValue>, Error> {
+            if age_group != AgeGroup::Adult {
+                return Err(Error::InvalidAgeGroup(age_group.to_string()));
+            }
+            let dcf = |value| {
+                Ok(vec![DcfValue {
+                    value,
+                    unit: "Sv/Bq".to_string(),
+                    attr: None,
+                }])
+            };
+
+            if nuclide == "Sr-90".parse().unwrap() {
+                dcf(2.8e-8)
+            } else if nuclide == "Y-90".parse().unwrap() {
+                dcf(2.7e-9)
+            } else {
+                Ok(vec![])
+            }
+        }
+    }
+
+    impl DcfInhalation for TestData {
+        fn dcf_inhalation(
+            &self,
+            _nuclide: Nuclide,
+            _age_group: AgeGroup,
+            _organ: Organ,
+        ) -> Result<Vec<DcfValue>, Error> {
+            Ok(vec![])
+        }
+    }
+
+    #[test]
+    fn dose_contribution_by_member() {
+        let sr90: Nuclide = "Sr-90".parse().unwrap();
+        let y90: Nuclide = "Y-90".parse().unwrap();
+
+        let fractions = TestData
+            .dose_contribution_by_member(sr90, 1e3, Pathway::Ingestion, AgeGroup::Adult)
+            .unwrap();
+
+        assert_eq!(fractions.len(), 2);
+        assert!((fractions[&sr90] - 2.8e-8 / (2.8e-8 + 2.7e-9)).abs() < 1e-12);
+        assert!(fractions[&sr90] > 0.9);
+        assert!((fractions.values().sum::<f64>() - 1.).abs() < 1e-12);
+        assert!(fractions[&y90] < 0.1);
+
+        assert!(matches!(
+            TestData.dose_contribution_by_member(sr90, 1e3, Pathway::Inhalation, AgeGroup::Adult),
+            Err(Error::NoDoseCoefficient(..))
+        ));
+        assert!(matches!(
+            TestData.dose_contribution_by_member(sr90, 1e3, Pathway::Ingestion, AgeGroup::OneYear),
+            Err(Error::InvalidAgeGroup(_))
+        ));
+    }
+
+    #[test]
+    fn committed_effective() {
+        let sr90: Nuclide = "Sr-90".parse().unwrap();
+        let y90: Nuclide = "Y-90".parse().unwrap();
+        let dose = |root| {
+            TestData
+                .committed_effective(root, Pathway::Ingestion, AgeGroup::Adult, 1e3)
+                .unwrap()
+        };
+
+        // Zr-90 is stable and has no coefficient
+        assert!((dose(y90) - 1e3 * 2.7e-9).abs() < 1e-18);
+        assert!((dose(sr90) - 1e3 * (2.8e-8 + 2.7e-9)).abs() < 1e-18);
+        assert!(dose(sr90) > 1.09 * 1e3 * 2.8e-8);
+
+        assert!(matches!(
+            TestData.committed_effective(sr90, Pathway::GroundSurface, AgeGroup::Adult, 1e3),
+            Err(Error::InvalidPathway(_))
+        ));
+        assert!(matches!(
+            TestData.committed_effective(sr90, Pathway::Inhalation, AgeGroup::Adult, 1e3),
+            Err(Error::NoDoseCoefficient(..))
+        ));
+        assert!(matches!(
+            TestData.committed_effective(sr90, Pathway::Ingestion, AgeGroup::OneYear, 1e3),
+            Err(Error::InvalidAgeGroup(_))
+        ));
+    }
+
+    #[test]
+    fn dose_by_member_over_period() {
+        let sr90: Nuclide = "Sr-90".parse().unwrap();
+        let y90: Nuclide = "Y-90".parse().unwrap();
+        let fifty_years = 50. * 365.25 * 86400.;
+        let dose = |root, period| {
+            TestData
+                .dose_by_member_over_period(root, 1e3, Pathway::Ingestion, AgeGroup::Adult, period)
+                .unwrap()
+        };
+
+        // short-lived nuclide is fully committed within 50 years
+        let finite = dose(y90, fifty_years);
+        let infinite = dose(y90, f64::INFINITY);
+        assert!((infinite[&y90] - 1e3 * 2.7e-9).abs() < 1e-15);
+        assert!((finite[&y90] - infinite[&y90]).abs() / infinite[&y90] < 1e-9);
+
+        // while long-lived one is not
+        let finite = dose(sr90, fifty_years);
+        let infinite = dose(sr90, f64::INFINITY);
+        assert!(finite[&sr90] < 0.8 * infinite[&sr90]);
+        assert!(finite[&sr90] > 0.6 * infinite[&sr90]);
+
+        // members without a coefficient are omitted, while other errors are not
+        assert!(!infinite.contains_key(&"Zr-90".parse().unwrap()));
+        assert!(matches!(
+            TestData.dose_by_member_over_period(
+                sr90,
+                1e3,
+                Pathway::Ingestion,
+                AgeGroup::OneYear,
+                fifty_years
+            ),
+            Err(Error::InvalidAgeGroup(_))
+        ));
+
+        for period in [-1., f64::NAN] {
+            assert!(matches!(
+                TestData.dose_by_member_over_period(
+                    sr90,
+                    1e3,
+                    Pathway::Ingestion,
+                    AgeGroup::Adult,
+                    period
+                ),
+                Err(Error::InvalidTime(_))
+            ));
+        }
+    }
+}
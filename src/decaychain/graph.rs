@@ -2,7 +2,7 @@ use std::collections::HashSet;
 use std::sync::Arc;
 
 use float_pretty_print::PrettyPrintFloat;
-use petgraph::{graph::NodeIndex, Graph};
+use petgraph::{graph::NodeIndex, Direction, Graph};
 
 use crate::primitive::attr::{NuclideHalfLife, NuclideProgeny};
 use crate::primitive::{DecayModeSet, HalfLife, Nuclide};
@@ -49,6 +49,28 @@ impl std::fmt::Display for ChainEdge {
 
 pub type DecayChain = Graph<ChainNode, ChainEdge>;
 
+/// Reverse lookup of progeny in a decay chain
+pub trait ChainParents {
+    /// Members decaying directly into nuclide, sorted and without duplicates
+    ///
+    /// It is empty for the root of chain and for nuclides not in chain.
+    fn get_parents(&self, nuclide: &Nuclide) -> Vec<Nuclide>;
+}
+
+impl ChainParents for DecayChain {
+    fn get_parents(&self, nuclide: &Nuclide) -> Vec<Nuclide> {
+        let mut parents: Vec<Nuclide> = self
+            .node_indices()
+            .filter(|&i| self[i].nuclide == *nuclide)
+            .flat_map(|i| self.neighbors_directed(i, Direction::Incoming))
+            .map(|i| self[i].nuclide)
+            .collect();
+        parents.sort();
+        parents.dedup();
+        parents
+    }
+}
+
 pub struct DecayChainBuilder<D> {
     data: Arc<D>,
 }
@@ -161,11 +183,13 @@ mod test {
                 Ok(HalfLife {
                     value: 2.7489,
                     unit: TimeUnit::Day,
+                    uncertainty: None,
                 })
             } else if nuclide == self.tc99m {
                 Ok(HalfLife {
                     value: 6.0067,
                     unit: TimeUnit::Hour,
+                    uncertainty: None,
                 })
             } else {
                 Err(Error::InvalidNuclide("not found".to_string()))
@@ -196,7 +220,8 @@ mod test {
             nodes[0].weight.half_life,
             Some(HalfLife {
                 value: 2.7489,
-                unit: TimeUnit::Day
+                unit: TimeUnit::Day,
+                uncertainty: None,
             })
         );
         assert_eq!(nodes[1].weight.nuclide, data.tc99m);
@@ -205,6 +230,7 @@ mod test {
             Some(HalfLife {
                 value: 6.0067,
                 unit: TimeUnit::Hour,
+                uncertainty: None,
             })
         );
 
@@ -216,4 +242,14 @@ mod test {
             DecayModeSet::default() | DecayMode::IsometricTransition
         );
     }
+
+    #[test]
+    fn chain_parents() {
+        let data = Arc::new(TestData::new());
+        let chain = DecayChainBuilder::new(data.clone()).build(data.mo99);
+
+        assert_eq!(chain.get_parents(&data.tc99m), vec![data.mo99]);
+        assert!(chain.get_parents(&data.mo99).is_empty());
+        assert!(chain.get_parents(&"U-238".parse().unwrap()).is_empty());
+    }
 }
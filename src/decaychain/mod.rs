@@ -1,11 +1,16 @@
+mod dose;
 mod graph;
+mod tree;
 
-pub use graph::{DecayChain, DecayChainBuilder};
+pub use dose::ChainDose;
+pub use graph::{ChainParents, DecayChain, DecayChainBuilder};
+pub use tree::{ChainTree, ChainTreeNode};
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::ops::Deref;
 use std::sync::{Arc, RwLock};
 
+use crate::error::Error;
 use crate::primitive::attr::{DecayConstant, NuclideProgeny};
 use crate::primitive::Nuclide;
 
@@ -41,6 +46,102 @@ impl Default for Inventory {
     }
 }
 
+pub trait DecaySeries {
+    /// All nuclides reachable by decay of nuclide, excluding itself unless it loops back
+    fn descendants(&self, nuclide: Nuclide) -> Result<BTreeSet<Nuclide>, Error>;
+
+    /// Fraction of decays of root passing through each member of its chain
+    ///
+    /// Branching rates are multiplied along each decay path and summed over paths, with
+    /// the root itself of yield 1. Paths looping back to a member are not followed.
+    fn cumulative_yields(&self, root: Nuclide) -> Result<BTreeMap<Nuclide, f64>, Error>;
+
+    /// Whether one nuclide is an ancestor or a descendant of the other
+    ///
+    /// A nuclide absent in data (e.g. a stable end product) has no descendants, but may
+    /// still be found in the series of the other one.
+    fn same_series(&self, a: &Nuclide, b: &Nuclide) -> Result<bool, Error> {
+        if a == b {
+            return Ok(true);
+        }
+
+        match (self.descendants(*a), self.descendants(*b)) {
+            (Err(e), Err(_)) => Err(e),
+            (a_desc, b_desc) => {
+                Ok(a_desc.is_ok_and(|d| d.contains(b)) || b_desc.is_ok_and(|d| d.contains(a)))
+            }
+        }
+    }
+}
+
+impl<T> DecaySeries for T
+where
+    T: NuclideProgeny,
+{
+    fn descendants(&self, nuclide: Nuclide) -> Result<BTreeSet<Nuclide>, Error> {
+        let mut descendants = BTreeSet::new();
+        let mut stack = vec![];
+        for daughter in self.progeny(nuclide)? {
+            stack.push(daughter.nuclide);
+        }
+
+        while let Some(parent) = stack.pop() {
+            if descendants.insert(parent) {
+                if let Ok(progeny) = self.progeny(parent) {
+                    stack.extend(progeny.into_iter().map(|d| d.nuclide));
+                }
+            }
+        }
+
+        Ok(descendants)
+    }
+
+    fn cumulative_yields(&self, root: Nuclide) -> Result<BTreeMap<Nuclide, f64>, Error> {
+        let mut yields = BTreeMap::from([(root, 1.)]);
+        let mut stack = vec![];
+        for daughter in self.progeny(root)? {
+            stack.push((daughter.nuclide, daughter.branch_rate, vec![root]));
+        }
+
+        while let Some((nuclide, y, mut path)) = stack.pop() {
+            if path.contains(&nuclide) {
+                continue;
+            }
+            *yields.entry(nuclide).or_insert(0.) += y;
+
+            if let Ok(progeny) = self.progeny(nuclide) {
+                path.push(nuclide);
+                for daughter in progeny {
+                    stack.push((daughter.nuclide, y * daughter.branch_rate, path.clone()));
+                }
+            }
+        }
+
+        Ok(yields)
+    }
+}
+
+// Number of log-spaced times searched for the first crossing of a total activity
+const CHAIN_TIME_STEPS: usize = 1200;
+
+// Decades of time below max_time covered by the search
+const CHAIN_TIME_DECADES: f64 = 12.;
+
+// Ratio of parent to daughter decay constants below which equilibrium is secular, where
+// daughter activity deviates from that of parent by the ratio
+const SECULAR_LAMBDA_RATIO: f64 = 1e-3;
+
+/// Kind of radioactive equilibrium between a parent and its descendant
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Equilibrium {
+    /// Parent is much longer-lived, and activities of both become equal
+    Secular,
+    /// Parent is longer-lived, and activities of both reach a constant ratio
+    Transient,
+    /// Parent is not longer-lived than daughter
+    NoEquilibrium,
+}
+
 type CachedNode = BTreeMap<Nuclide, Vec<(Vec<f64>, Vec<f64>)>>;
 type CachedData = BTreeMap<Nuclide, Arc<CachedNode>>;
 
@@ -84,14 +185,7 @@ where
                 for (br, lamb) in vars {
                     *res.entry(nuc).or_insert(0.) += lamb[1..].iter().product::<f64>()
                         * br.iter().product::<f64>()
-                        * (lamb.iter().enumerate())
-                            .map(|(i, &li)| {
-                                (-li * dt).exp()
-                                    / (lamb.iter().enumerate().filter(|(j, _)| i != *j))
-                                        .map(|(_, &lj)| lj - li)
-                                        .product::<f64>()
-                            })
-                            .sum::<f64>();
+                        * bateman_sum(lamb, dt);
                 }
             }
 
@@ -101,6 +195,270 @@ where
         }
     }
 
+    /// Activities (Bq) of parent and its progeny at time t (s) from a pure parent of
+    /// initial activity a0 (Bq).
+    ///
+    /// Activities along every branch are summed, stable end products have zero activity,
+    /// and members with (nearly) equal decay constants are evaluated by the limit of the
+    /// Bateman equation.
+    pub fn activities_at(
+        &self,
+        parent: Nuclide,
+        initial_activity_bq: f64,
+        t_secs: f64,
+    ) -> Result<HashMap<Nuclide, f64>, Error> {
+        let res = self
+            .bateman_eq(parent, t_secs)
+            .ok_or_else(|| Error::InvalidNuclide(parent.to_string()))?;
+
+        Ok(res
+            .into_iter()
+            .map(|(nuclide, a)| (nuclide, initial_activity_bq * a))
+            .collect())
+    }
+
+    /// Activities (Bq) of parent and its progeny at each of ascending times (s) from a pure
+    /// parent of initial activity a0 (Bq), as series aligned to `times`
+    ///
+    /// Stable end products are tracked with zero activity, as in [`Self::activities_at`].
+    pub fn evolve(
+        &self,
+        parent: Nuclide,
+        initial_activity_bq: f64,
+        times: &[f64],
+    ) -> Result<HashMap<Nuclide, Vec<f64>>, Error> {
+        if let Some(t) = times.iter().find(|t| !(t.is_finite() && **t >= 0.)) {
+            return Err(Error::InvalidTime(format!("{} s", t)));
+        }
+        if let Some(w) = times.windows(2).find(|w| w[0] > w[1]) {
+            return Err(Error::InvalidTime(format!(
+                "{} s after {} s in ascending times",
+                w[1], w[0]
+            )));
+        }
+
+        let mut series: HashMap<Nuclide, Vec<f64>> = HashMap::new();
+        for (i, &t) in times.iter().enumerate() {
+            for (nuclide, a) in self.activities_at(parent, initial_activity_bq, t)? {
+                series
+                    .entry(nuclide)
+                    .or_insert_with(|| vec![0.; times.len()])[i] = a;
+            }
+        }
+
+        Ok(series)
+    }
+
+    /// Number of decays of parent and its progeny within `[t0, t1]` (s) from a pure parent
+    /// of initial activity a0 (Bq) at time 0
+    ///
+    /// The Bateman activity of each member is integrated in closed form, which is the
+    /// Bateman sum with an extra decay constant of zero, so (nearly) equal decay constants
+    /// are handled as in [`Self::activities_at`]. Stable end products have no decays.
+    /// `t1` may be infinite for all decays after `t0`, where only the term of the extra
+    /// zero decay constant is left, as in [`DecayConstant::integrated_decays`].
+    pub fn integrated_decays(
+        &self,
+        parent: Nuclide,
+        initial_activity_bq: f64,
+        t0_secs: f64,
+        t1_secs: f64,
+    ) -> Result<HashMap<Nuclide, f64>, Error> {
+        let ordered = 0. <= t0_secs && t0_secs <= t1_secs && t0_secs.is_finite();
+        if !ordered {
+            return Err(Error::InvalidTime(format!(
+                "interval [{}, {}] s",
+                t0_secs, t1_secs
+            )));
+        }
+
+        let cache = self
+            .cached_vars(parent)
+            .ok_or_else(|| Error::InvalidNuclide(parent.to_string()))?;
+
+        let mut res = HashMap::new();
+        for (&nuc, vars) in cache.iter() {
+            for (br, lamb) in vars {
+                let mut lamb_0 = lamb.clone();
+                lamb_0.push(0.);
+                let coef = initial_activity_bq
+                    * lamb[1..].iter().product::<f64>()
+                    * br.iter().product::<f64>();
+                let upper = if t1_secs.is_infinite() {
+                    lamb.iter().product::<f64>().recip()
+                } else {
+                    bateman_sum(&lamb_0, t1_secs)
+                };
+                *res.entry(nuc).or_insert(0.) += if coef == 0. {
+                    0.
+                } else {
+                    coef * (upper - bateman_sum(&lamb_0, t0_secs))
+                };
+            }
+        }
+
+        Ok(res)
+    }
+
+    /// Activity of target progeny at time t (s) from a pure parent of initial activity a0.
+    pub fn progeny_activity(
+        &self,
+        parent: Nuclide,
+        target: Nuclide,
+        a0: f64,
+        t: f64,
+    ) -> Result<f64, Error> {
+        if !self.decay_data.descendants(parent)?.contains(&target) {
+            return Err(Error::NotDescendant(target, parent));
+        }
+
+        let res = self
+            .bateman_eq(parent, t)
+            .ok_or_else(|| Error::InvalidNuclide(parent.to_string()))?;
+
+        Ok(a0 * res.get(&target).copied().unwrap_or(0.))
+    }
+
+    /// Time (s) at which the activity of daughter is maximal, starting from a pure parent.
+    pub fn daughter_peak_time(&self, parent: Nuclide, daughter: Nuclide) -> Result<f64, Error> {
+        if !self
+            .decay_data
+            .progeny(parent)?
+            .iter()
+            .any(|p| p.nuclide == daughter)
+        {
+            return Err(Error::NotDaughter(daughter, parent));
+        }
+
+        let lambda_p = self.decay_data.lambda(parent)?;
+        let lambda_d = self.decay_data.lambda(daughter)?;
+
+        if lambda_p == 0. || lambda_d == 0. {
+            let stable = if lambda_p == 0. { parent } else { daughter };
+            Err(Error::InvalidHalfLife(format!("{} is stable", stable)))
+        } else if lambda_p == lambda_d {
+            Err(Error::EqualDecayConstants(parent, daughter))
+        } else {
+            Ok((lambda_d / lambda_p).ln() / (lambda_d - lambda_p))
+        }
+    }
+
+    /// Fraction of parent remaining when the activity of daughter is maximal.
+    pub fn parent_fraction_at_daughter_peak(
+        &self,
+        parent: Nuclide,
+        daughter: Nuclide,
+    ) -> Result<f64, Error> {
+        let t = self.daughter_peak_time(parent, daughter)?;
+        Ok((-self.decay_data.lambda(parent)? * t).exp())
+    }
+
+    /// Kind of equilibrium between parent and daughter by their decay constants
+    ///
+    /// Equilibrium is secular if `λp / λd` is below 1e-3, and transient if it is below 1.
+    pub fn equilibrium_kind(
+        &self,
+        parent: Nuclide,
+        daughter: Nuclide,
+    ) -> Result<Equilibrium, Error> {
+        let (lambda_p, lambda_d) = self.equilibrium_lambdas(parent, daughter)?;
+
+        if lambda_p >= lambda_d {
+            Ok(Equilibrium::NoEquilibrium)
+        } else if lambda_p < SECULAR_LAMBDA_RATIO * lambda_d {
+            Ok(Equilibrium::Secular)
+        } else {
+            Ok(Equilibrium::Transient)
+        }
+    }
+
+    /// Time (s) for daughter to reach fraction of its equilibrium activity, starting
+    /// from a pure parent
+    ///
+    /// Daughter grows in as `1 - exp(-(λd - λp) t)` of its equilibrium activity, which
+    /// neglects members between parent and daughter.
+    pub fn time_to_equilibrium(
+        &self,
+        parent: Nuclide,
+        daughter: Nuclide,
+        fraction: f64,
+    ) -> Result<f64, Error> {
+        if !(fraction > 0. && fraction < 1.) {
+            return Err(Error::InvalidFraction(fraction.to_string()));
+        }
+
+        let (lambda_p, lambda_d) = self.equilibrium_lambdas(parent, daughter)?;
+        if lambda_p >= lambda_d {
+            return Err(Error::NoEquilibrium(parent, daughter));
+        }
+
+        Ok(-(-fraction).ln_1p() / (lambda_d - lambda_p))
+    }
+
+    // Decay constants of parent and its descendant
+    fn equilibrium_lambdas(&self, parent: Nuclide, daughter: Nuclide) -> Result<(f64, f64), Error> {
+        if !self.decay_data.descendants(parent)?.contains(&daughter) {
+            return Err(Error::NotDescendant(daughter, parent));
+        }
+
+        Ok((
+            self.decay_data.lambda(parent)?,
+            self.decay_data.lambda(daughter)?,
+        ))
+    }
+
+    /// Time (s) for total activity of the chain of root to drop to fraction of a0.
+    ///
+    /// Starting from a pure root of activity a0, the first time the sum of activities of
+    /// all members reaches `fraction * a0` is searched on a logarithmic time grid up to
+    /// `max_time_s` and refined by bisection, as in-growth of progeny may raise the total
+    /// activity above a0 first.
+    pub fn chain_time_to_fraction(
+        &self,
+        root: Nuclide,
+        a0: f64,
+        fraction: f64,
+        max_time_s: f64,
+    ) -> Result<f64, Error> {
+        if !(fraction > 0. && fraction < 1.) {
+            return Err(Error::InvalidFraction(fraction.to_string()));
+        }
+        if !(a0 > 0. && a0.is_finite()) {
+            return Err(Error::InvalidActivity(format!("{} Bq", a0)));
+        }
+        if !(max_time_s > 0. && max_time_s.is_finite()) {
+            return Err(Error::InvalidTime(format!("{} s", max_time_s)));
+        }
+
+        let target = fraction * a0;
+        let total = |t: f64| -> Result<f64, Error> {
+            self.bateman_eq(root, t)
+                .map(|res| a0 * res.values().sum::<f64>())
+                .ok_or_else(|| Error::InvalidNuclide(root.to_string()))
+        };
+
+        let mut lo = 0.;
+        for i in 0..=CHAIN_TIME_STEPS {
+            let decades = CHAIN_TIME_DECADES * (1. - i as f64 / CHAIN_TIME_STEPS as f64);
+            let hi = max_time_s * 10f64.powf(-decades);
+            if total(hi)? <= target {
+                let mut hi = hi;
+                while hi - lo > hi * f64::EPSILON * 4. {
+                    let mid = 0.5 * (lo + hi);
+                    if total(mid)? <= target {
+                        hi = mid;
+                    } else {
+                        lo = mid;
+                    }
+                }
+                return Ok(hi);
+            }
+            lo = hi;
+        }
+
+        Err(Error::ActivityNotReached(root, target, max_time_s))
+    }
+
     // Variables for calculate with Bateman Equation
     fn cached_vars(&self, parent: Nuclide) -> Option<Arc<CachedNode>> {
         let cache = self.cache.read().unwrap();
@@ -121,7 +479,7 @@ where
                     .or_default()
                     .push((br.clone(), lambda.clone()));
 
-                for daughter in self.decay_data.progeny(parent).unwrap() {
+                for daughter in self.decay_data.progeny(parent).unwrap_or_default() {
                     if let Ok(lambda_d) = self.decay_data.lambda(daughter.nuclide) {
                         let mut br = br.clone();
                         br.push(daughter.branch_rate);
@@ -140,14 +498,58 @@ where
     }
 }
 
+// Relative difference of decay constants regarded as equal
+const DEGENERATE_LAMBDA: f64 = 1e-9;
+
+// Sum of exp(-λi t) / Π_j≠i (λj - λi) over decay constants of a chain
+//
+// The sum is (-1)^n times divided difference of exp(-λt) over λ, which is evaluated by
+// derivatives of exp(-λt) where decay constants are (nearly) equal.
+fn bateman_sum(lamb: &[f64], t: f64) -> f64 {
+    let scale = lamb.iter().fold(0f64, |a, l| a.max(l.abs()));
+    let degenerate = |a: f64, b: f64| (a - b).abs() <= DEGENERATE_LAMBDA * scale;
+
+    let distinct = lamb
+        .iter()
+        .enumerate()
+        .all(|(i, &li)| lamb[i + 1..].iter().all(|&lj| !degenerate(li, lj)));
+
+    if distinct {
+        return (lamb.iter().enumerate())
+            .map(|(i, &li)| {
+                (-li * t).exp()
+                    / (lamb.iter().enumerate().filter(|(j, _)| i != *j))
+                        .map(|(_, &lj)| lj - li)
+                        .product::<f64>()
+            })
+            .sum::<f64>();
+    }
+
+    let mut x = lamb.to_vec();
+    x.sort_by(f64::total_cmp);
+    let n = x.len() - 1;
+
+    let mut diff: Vec<f64> = x.iter().map(|&xi| (-xi * t).exp()).collect();
+    let mut factorial = 1.;
+    for k in 1..=n {
+        factorial *= k as f64;
+        for i in 0..=(n - k) {
+            diff[i] = if degenerate(x[i + k], x[i]) {
+                (-t).powi(k as i32) * (-x[i] * t).exp() / factorial
+            } else {
+                (diff[i + 1] - diff[i]) / (x[i + k] - x[i])
+            };
+        }
+    }
+
+    (-1f64).powi(n as i32) * diff[0]
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
 
-    use crate::{
-        error::Error,
-        primitive::{DecayModeSet, Progeny},
-    };
+    use crate::primitive::{DecayModeSet, Progeny};
 
     struct TestData {
         progeny: BTreeMap<Nuclide, Vec<Progeny>>,
@@ -177,6 +579,21 @@ mod test {
             insert_progeny!("Nb-99", "Mo-99", 0.7);
             insert_progeny!("Mo-99", "Tc-99m", 0.3);
             insert_progeny!("Tc-99m");
+            insert_progeny!("Te-132", "I-132", 1.0);
+            insert_progeny!("I-132");
+            insert_progeny!("U-238", "Th-234", 1.0);
+            insert_progeny!("Th-234", "Pa-234m", 1.0);
+            insert_progeny!("Pa-234m", "U-234", 0.9984);
+            insert_progeny!("U-234", "Th-230", 1.0);
+            insert_progeny!("Th-230", "Ra-226", 1.0);
+            insert_progeny!("Ra-226", "Rn-222", 1.0);
+            insert_progeny!("Co-60", "Ni-60", 1.0);
+            insert_progeny!("Sr-90", "Y-90", 1.0);
+            insert_progeny!("Y-90", "Zr-90", 1.0);
+            insert_progeny!("Zr-90");
+            // artificial pair of equal decay constants
+            insert_progeny!("Sn-121m", "Sn-121", 1.0);
+            insert_progeny!("Sn-121");
 
             Arc::new(Self { progeny })
         }
@@ -199,6 +616,23 @@ mod test {
                 Ok(2.0_f64.ln() / 2.)
             } else if nuclide == "Tc-99m".parse().unwrap() {
                 Ok(2.0_f64.ln() / 4.)
+            } else if nuclide == "Te-132".parse().unwrap() {
+                Ok(2.0_f64.ln() / 76.9)
+            } else if nuclide == "I-132".parse().unwrap() {
+                Ok(2.0_f64.ln() / 2.295)
+            } else if nuclide == "Sr-90".parse().unwrap() {
+                Ok(2.0_f64.ln() / (28.79 * 365.2422 * 86400.))
+            } else if nuclide == "Y-90".parse().unwrap() {
+                Ok(2.0_f64.ln() / (64.05 * 3600.))
+            } else if nuclide == "Zr-90".parse().unwrap() {
+                Ok(0.)
+            } else if nuclide == "Ra-226".parse().unwrap() {
+                Ok(2.0_f64.ln() / (1600. * 365.2422 * 86400.))
+            } else if nuclide == "Rn-222".parse().unwrap() {
+                Ok(2.0_f64.ln() / (3.8235 * 86400.))
+            } else if nuclide == "Sn-121m".parse().unwrap() || nuclide == "Sn-121".parse().unwrap()
+            {
+                Ok(0.1)
             } else {
                 Err(Error::InvalidNuclide(nuclide.to_string()))
             }
@@ -238,4 +672,368 @@ mod test {
             )
         );
     }
+
+    #[test]
+    fn same_series() {
+        let data = TestData::new();
+        let n = |s: &str| s.parse::<Nuclide>().unwrap();
+
+        assert!(data.descendants(n("U-238")).unwrap().contains(&n("Rn-222")));
+        assert!(data.same_series(&n("U-238"), &n("Ra-226")).unwrap());
+        assert!(data.same_series(&n("Ra-226"), &n("U-238")).unwrap());
+        assert!(data.same_series(&n("Ni-60"), &n("Co-60")).unwrap());
+        assert!(!data.same_series(&n("U-238"), &n("Co-60")).unwrap());
+        assert!(!data.same_series(&n("Ra-226"), &n("Ni-60")).unwrap());
+        assert!(data.same_series(&n("Ni-60"), &n("Pb-206")).is_err());
+    }
+
+    #[test]
+    fn progeny_activity() {
+        let data = TestData::new();
+        let solver = BatemanDecaySolver::new(data);
+
+        let te132: Nuclide = "Te-132".parse().unwrap();
+        let i132: Nuclide = "I-132".parse().unwrap();
+
+        let lp = 2.0_f64.ln() / 76.9;
+        let ld = 2.0_f64.ln() / 2.295;
+        let t = 10.;
+        let expected = 5. * ld / (ld - lp) * ((-lp * t).exp() - (-ld * t).exp());
+        let activity = solver.progeny_activity(te132, i132, 5., t).unwrap();
+        assert!((activity - expected).abs() / expected < 1e-12);
+
+        assert!(matches!(
+            solver.progeny_activity(i132, te132, 5., t),
+            Err(Error::NotDescendant(..))
+        ));
+        assert!(matches!(
+            solver.progeny_activity(te132, "Tc-99m".parse().unwrap(), 5., t),
+            Err(Error::NotDescendant(..))
+        ));
+    }
+
+    #[test]
+    fn integrated_decays() {
+        let data = TestData::new();
+        let solver = BatemanDecaySolver::new(data.clone());
+        let n = |s: &str| s.parse::<Nuclide>().unwrap();
+
+        // Y-90 decays as often as Sr-90 in secular equilibrium
+        let ten_years = 10. * 365.2422 * 86400.;
+        let decays = solver
+            .integrated_decays(n("Sr-90"), 1000., 0., ten_years)
+            .unwrap();
+        let sr90 = data
+            .integrated_decays(n("Sr-90"), 1000., 0., ten_years)
+            .unwrap();
+        assert!((decays[&n("Sr-90")] - sr90).abs() / sr90 < 1e-9);
+        // less the in-growth of Y-90 over its mean life of 3.85 days
+        assert!((decays[&n("Y-90")] / sr90 - 1.).abs() < 2e-3);
+        assert!(decays[&n("Y-90")] < sr90);
+        assert_eq!(decays[&n("Zr-90")], 0.);
+        let decays_10y_y90 = decays[&n("Y-90")];
+
+        // daughter of equal decay constant has activity a0 λt exp(-λt)
+        let l = 0.1;
+        let decays = solver.integrated_decays(n("Sn-121m"), 1., 0., 10.).unwrap();
+        let expected = (1. - (-l * 10f64).exp() * (1. + l * 10.)) / l;
+        assert!((decays[&n("Sn-121")] - expected).abs() / expected < 1e-9);
+        let decays = solver
+            .integrated_decays(n("Sn-121m"), 1., 0., f64::INFINITY)
+            .unwrap();
+        assert!((decays[&n("Sn-121")] * l - 1.).abs() < 1e-9);
+
+        let decays = solver.integrated_decays(n("Sn-121m"), 1., 5., 5.).unwrap();
+        assert!(decays.values().all(|d| d.abs() < 1e-12));
+
+        assert!(matches!(
+            solver.integrated_decays(n("Sr-90"), 1000., 10., 0.),
+            Err(Error::InvalidTime(_))
+        ));
+        // every atom decays once through each member after all
+        let all = solver
+            .integrated_decays(n("Sr-90"), 1000., 0., f64::INFINITY)
+            .unwrap();
+        let sr90 = data
+            .integrated_decays(n("Sr-90"), 1000., 0., f64::INFINITY)
+            .unwrap();
+        assert!((all[&n("Sr-90")] - sr90).abs() / sr90 < 1e-9);
+        assert!((all[&n("Y-90")] - sr90).abs() / sr90 < 1e-9);
+        assert_eq!(all[&n("Zr-90")], 0.);
+        let rest = solver
+            .integrated_decays(n("Sr-90"), 1000., ten_years, f64::INFINITY)
+            .unwrap();
+        assert!((decays_10y_y90 + rest[&n("Y-90")] - all[&n("Y-90")]).abs() / sr90 < 1e-9);
+        assert!(matches!(
+            solver.integrated_decays(n("Sr-90"), 1000., f64::INFINITY, f64::INFINITY),
+            Err(Error::InvalidTime(_))
+        ));
+        assert!(solver
+            .integrated_decays(n("Pb-206"), 1000., 0., 1.)
+            .is_err());
+    }
+
+    #[test]
+    fn cumulative_yields() {
+        let data = TestData::new();
+        let n = |s: &str| s.parse::<Nuclide>().unwrap();
+
+        let yields = data.cumulative_yields(n("Nb-99")).unwrap();
+        assert_eq!(yields.len(), 3);
+        assert_eq!(yields[&n("Nb-99")], 1.);
+        assert_eq!(yields[&n("Mo-99")], 0.7);
+        assert!((yields[&n("Tc-99m")] - 0.21).abs() < 1e-12);
+
+        let yields = data.cumulative_yields(n("U-238")).unwrap();
+        assert!((yields[&n("Rn-222")] - 0.9984).abs() < 1e-12);
+    }
+
+    #[test]
+    fn daughter_peak_time() {
+        let data = TestData::new();
+        let solver = BatemanDecaySolver::new(data);
+
+        let te132: Nuclide = "Te-132".parse().unwrap();
+        let i132: Nuclide = "I-132".parse().unwrap();
+
+        let lp = 2.0_f64.ln() / 76.9;
+        let ld = 2.0_f64.ln() / 2.295;
+        let t = solver.daughter_peak_time(te132, i132).unwrap();
+        assert!((t - (ld / lp).ln() / (ld - lp)).abs() < 1e-9);
+
+        let activity = |t| solver.bateman_eq(te132, t).unwrap()[&i132];
+        assert!(activity(t) > activity(t - 0.1));
+        assert!(activity(t) > activity(t + 0.1));
+
+        let fraction = solver
+            .parent_fraction_at_daughter_peak(te132, i132)
+            .unwrap();
+        assert!((fraction - (-lp * t).exp()).abs() < 1e-12);
+
+        assert!(matches!(
+            solver.daughter_peak_time("Nb-99".parse().unwrap(), "Tc-99m".parse().unwrap()),
+            Err(Error::NotDaughter(..))
+        ));
+        assert!(matches!(
+            solver.daughter_peak_time("Sn-121m".parse().unwrap(), "Sn-121".parse().unwrap()),
+            Err(Error::EqualDecayConstants(..))
+        ));
+    }
+
+    // Mo-99/Tc-99m generator with decay data of ICRP-07
+    struct Generator;
+
+    impl NuclideProgeny for Generator {
+        fn progeny(&self, nuclide: Nuclide) -> Result<Vec<Progeny>, Error> {
+            if nuclide == "Mo-99".parse().unwrap() {
+                Ok(vec![Progeny {
+                    nuclide: "Tc-99m".parse().unwrap(),
+                    branch_rate: 0.8773,
+                    decay_mode: DecayModeSet::default(),
+                }])
+            } else {
+                Err(Error::InvalidNuclide(nuclide.to_string()))
+            }
+        }
+    }
+
+    impl DecayConstant for Generator {
+        fn lambda(&self, nuclide: Nuclide) -> Result<f64, Error> {
+            if nuclide == "Mo-99".parse().unwrap() {
+                Ok(2.0_f64.ln() / 65.94)
+            } else if nuclide == "Tc-99m".parse().unwrap() {
+                Ok(2.0_f64.ln() / 6.015)
+            } else {
+                Err(Error::InvalidNuclide(nuclide.to_string()))
+            }
+        }
+    }
+
+    #[test]
+    fn equilibrium_kind() {
+        let n = |s: &str| s.parse::<Nuclide>().unwrap();
+        let generator = BatemanDecaySolver::new(Arc::new(Generator));
+        let solver = BatemanDecaySolver::new(TestData::new());
+
+        assert_eq!(
+            generator.equilibrium_kind(n("Mo-99"), n("Tc-99m")).unwrap(),
+            Equilibrium::Transient
+        );
+        assert_eq!(
+            solver.equilibrium_kind(n("Te-132"), n("I-132")).unwrap(),
+            Equilibrium::Transient
+        );
+        assert_eq!(
+            solver.equilibrium_kind(n("Ra-226"), n("Rn-222")).unwrap(),
+            Equilibrium::Secular
+        );
+        assert_eq!(
+            solver.equilibrium_kind(n("Sr-90"), n("Y-90")).unwrap(),
+            Equilibrium::Secular
+        );
+        // daughter of the mock outlives its parent
+        assert_eq!(
+            solver.equilibrium_kind(n("Mo-99"), n("Tc-99m")).unwrap(),
+            Equilibrium::NoEquilibrium
+        );
+
+        assert!(matches!(
+            solver.equilibrium_kind(n("Y-90"), n("Sr-90")),
+            Err(Error::NotDescendant(..))
+        ));
+        assert!(matches!(
+            solver.equilibrium_kind(n("Sr-90"), n("Tc-99m")),
+            Err(Error::NotDescendant(..))
+        ));
+    }
+
+    #[test]
+    fn time_to_equilibrium() {
+        let n = |s: &str| s.parse::<Nuclide>().unwrap();
+        let generator = BatemanDecaySolver::new(Arc::new(Generator));
+        let (mo99, tc99m) = (n("Mo-99"), n("Tc-99m"));
+
+        // about four half-lives of Tc-99m for 95% of equilibrium activity
+        let t = generator.time_to_equilibrium(mo99, tc99m, 0.95).unwrap();
+        let (lp, ld) = (2.0_f64.ln() / 65.94, 2.0_f64.ln() / 6.015);
+        assert!((t - 20f64.ln() / (ld - lp)).abs() < 1e-9);
+        assert!(t > 4. * 6.015 && t < 5. * 6.015);
+
+        // daughter activity relative to its equilibrium value A_d / A_p = br λd / (λd - λp)
+        let res = generator.bateman_eq(mo99, t).unwrap();
+        let ratio = res[&tc99m] / res[&mo99] / (0.8773 * ld / (ld - lp));
+        assert!((ratio - 0.95).abs() < 1e-9);
+
+        let solver = BatemanDecaySolver::new(TestData::new());
+        let t = solver
+            .time_to_equilibrium(n("Ra-226"), n("Rn-222"), 0.5)
+            .unwrap();
+        assert!((t / (3.8235 * 86400.) - 1.).abs() < 1e-3);
+
+        assert!(matches!(
+            generator.time_to_equilibrium(mo99, tc99m, 1.),
+            Err(Error::InvalidFraction(_))
+        ));
+        assert!(matches!(
+            solver.time_to_equilibrium(n("Mo-99"), n("Tc-99m"), 0.5),
+            Err(Error::NoEquilibrium(..))
+        ));
+    }
+
+    #[test]
+    fn evolve() {
+        let n = |s: &str| s.parse::<Nuclide>().unwrap();
+        let generator = BatemanDecaySolver::new(Arc::new(Generator));
+        let (mo99, tc99m) = (n("Mo-99"), n("Tc-99m"));
+
+        // hours, as are the decay constants of the mock
+        let times = [0., 6., 23., 100., 300.];
+        let series = generator.evolve(mo99, 1e3, &times).unwrap();
+        assert_eq!(series.len(), 2);
+
+        let (mo, tc) = (&series[&mo99], &series[&tc99m]);
+        assert_eq!(mo.len(), times.len());
+        assert_eq!(mo[0], 1e3);
+        assert_eq!(tc[0], 0.);
+        assert!(tc[1] < tc[2]);
+        assert!(tc[2] > tc[3] && tc[3] > tc[4]);
+        for (i, &t) in times.iter().enumerate() {
+            let point = generator.activities_at(mo99, 1e3, t).unwrap();
+            assert_eq!(mo[i], point[&mo99]);
+            assert_eq!(tc[i], point[&tc99m]);
+        }
+
+        assert!(generator.evolve(mo99, 1e3, &[]).unwrap().is_empty());
+        for times in [&[6., 0.][..], &[-1., 0.], &[f64::NAN]] {
+            assert!(matches!(
+                generator.evolve(mo99, 1e3, times),
+                Err(Error::InvalidTime(_))
+            ));
+        }
+        assert!(generator.evolve(n("Co-60"), 1e3, &[0.]).is_err());
+    }
+
+    #[test]
+    fn chain_time_to_fraction() {
+        let data = TestData::new();
+        let solver = BatemanDecaySolver::new(data);
+        let te132: Nuclide = "Te-132".parse().unwrap();
+
+        let lp = 2.0_f64.ln() / 76.9;
+        let ld = 2.0_f64.ln() / 2.295;
+
+        // total activity rises with in-growth of I-132 before it drops
+        let total = |t| solver.bateman_eq(te132, t).unwrap().values().sum::<f64>();
+        assert!(total(5.) > 1.);
+
+        let t = solver.chain_time_to_fraction(te132, 2., 0.5, 1e4).unwrap();
+        // I-132 is in transient equilibrium by then
+        let expected = (2. * (2. * ld - lp) / (ld - lp)).ln() / lp;
+        assert!((t - expected).abs() / expected < 1e-9);
+        assert!(t > 76.9);
+
+        assert!(matches!(
+            solver.chain_time_to_fraction(te132, 2., 0.5, 100.),
+            Err(Error::ActivityNotReached(n, target, _)) if n == te132 && target == 1.
+        ));
+        assert!(matches!(
+            solver.chain_time_to_fraction(te132, 2., 1.5, 1e4),
+            Err(Error::InvalidFraction(_))
+        ));
+        assert!(matches!(
+            solver.chain_time_to_fraction(te132, 0., 0.5, 1e4),
+            Err(Error::InvalidActivity(_))
+        ));
+        assert!(matches!(
+            solver.chain_time_to_fraction(te132, 2., 0.5, f64::INFINITY),
+            Err(Error::InvalidTime(_))
+        ));
+    }
+
+    #[test]
+    fn activities_at() {
+        let data = TestData::new();
+        let solver = BatemanDecaySolver::new(data);
+        let n = |s: &str| s.parse::<Nuclide>().unwrap();
+
+        let l1 = 2.0_f64.ln() / (28.79 * 365.2422 * 86400.);
+        let l2 = 2.0_f64.ln() / (64.05 * 3600.);
+
+        for t in [0., 3600., 86400., 30. * 86400., 10. * 365.2422 * 86400.] {
+            let activities = solver.activities_at(n("Sr-90"), 1e3, t).unwrap();
+            assert_eq!(activities.len(), 3);
+
+            let sr90 = 1e3 * (-l1 * t).exp();
+            let y90 = 1e3 * l2 / (l2 - l1) * ((-l1 * t).exp() - (-l2 * t).exp());
+            assert!((activities[&n("Sr-90")] - sr90).abs() < 1e-9);
+            assert!((activities[&n("Y-90")] - y90).abs() < 1e-9);
+            assert_eq!(activities[&n("Zr-90")], 0.);
+        }
+
+        // secular equilibrium after a month
+        let activities = solver.activities_at(n("Sr-90"), 1e3, 60. * 86400.).unwrap();
+        assert!((activities[&n("Y-90")] / activities[&n("Sr-90")] - 1.).abs() < 1e-3);
+
+        // limit of equal decay constants: A2 = a0 λ t exp(-λt)
+        let activities = solver.activities_at(n("Sn-121m"), 1e3, 5.).unwrap();
+        let expected = 1e3 * 0.1 * 5. * (-0.5f64).exp();
+        assert!((activities[&n("Sn-121")] - expected).abs() / expected < 1e-12);
+        assert!(activities.values().all(|a| a.is_finite()));
+
+        assert!(solver.activities_at(n("Cs-137"), 1e3, 5.).is_err());
+    }
+
+    #[test]
+    fn bateman_sum_limits() {
+        // distinct constants
+        let sum = bateman_sum(&[1., 2.], 1.);
+        assert!((sum - ((-1f64).exp() - (-2f64).exp())).abs() < 1e-15);
+
+        // nearly equal constants approach the limit of equal ones
+        let near = bateman_sum(&[1., 1. + 1e-12, 2.], 1.);
+        let equal = bateman_sum(&[1., 1., 2.], 1.);
+        assert!((near - equal).abs() < 1e-9);
+        // t^2 / 2 exp(-λt) of three equal constants
+        assert!((bateman_sum(&[0.5; 3], 2.) - 2. * (-1f64).exp()).abs() < 1e-15);
+    }
 }
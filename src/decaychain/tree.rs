@@ -0,0 +1,418 @@
+use std::collections::HashSet;
+
+use float_pretty_print::PrettyPrintFloat;
+
+use crate::error::Error;
+use crate::primitive::attr::{NuclideHalfLife, NuclideProgeny};
+use crate::primitive::{DecayModeSet, HalfLife, Nuclide, Progeny};
+
+// Maximum depth of printed tree, deeper progeny are elided
+const MAX_TREE_DEPTH: usize = 32;
+
+/// Node of a decay chain tree
+#[derive(Debug, Clone)]
+pub struct ChainTreeNode {
+    pub nuclide: Nuclide,
+    /// Half-life, if known in data
+    pub half_life: Option<HalfLife>,
+    /// Branch rate from parent, which is 1 for root
+    pub branch_rate: f64,
+    /// Decay mode from parent, which is empty for root
+    pub decay_mode: DecayModeSet,
+    /// Whether nuclide is already an ancestor in this branch, which is not expanded
+    pub back_reference: bool,
+    pub children: Vec<ChainTreeNode>,
+}
+
+impl ChainTreeNode {
+    /// Number of nodes in tree, including back references
+    pub fn size(&self) -> usize {
+        1 + self.children.iter().map(|c| c.size()).sum::<usize>()
+    }
+
+    /// Depth of tree, which is 1 for a single node
+    pub fn depth(&self) -> usize {
+        1 + self.children.iter().map(|c| c.depth()).max().unwrap_or(0)
+    }
+}
+
+pub trait ChainTree {
+    /// Decay chain of root as an indented tree, e.g.
+    ///
+    /// ```text
+    /// Bi-212 (60.55 m)
+    /// ├── β- 64.06% Po-212 (0.299 μs)
+    /// │   └── ⍺ 100% Pb-208
+    /// └── ⍺ 35.94% Tl-208 (3.053 m)
+    ///     └── β- 100% Pb-208
+    /// ```
+    ///
+    /// A nuclide which is already an ancestor in its branch is marked as `(cycle)` and
+    /// not expanded again.
+    fn chain_tree_string(&self, root: Nuclide) -> Result<String, Error>;
+
+    /// Decay chain of root as a tree
+    ///
+    /// A nuclide which is already an ancestor in its branch is kept as a back reference
+    /// and not expanded again. Daughters without any data are leaves, as stable end
+    /// products are absent in datasets such as ICRP-07, but a daughter with a finite
+    /// half-life and no progeny data is an error.
+    fn build_tree(&self, root: Nuclide) -> Result<ChainTreeNode, Error>;
+
+    /// Decay chain of root as a Graphviz DOT digraph
+    ///
+    /// Each nuclide is declared once, labeled with its half-life, and stable or unknown
+    /// end products are drawn as boxes. Edges are labeled with decay mode and branch
+    /// rate, and a back reference is drawn as a single edge to the existing node.
+    fn to_dot(&self, root: Nuclide) -> Result<String, Error> {
+        let tree = self.build_tree(root)?;
+
+        let mut nodes = vec![];
+        let mut edges = vec![];
+        let mut seen = HashSet::new();
+        write_dot(&tree, &mut seen, &mut nodes, &mut edges);
+
+        Ok(format!(
+            "digraph \"{}\" {{\n{}{}}}\n",
+            root,
+            nodes.concat(),
+            edges.concat()
+        ))
+    }
+}
+
+impl<T> ChainTree for T
+where
+    T: NuclideHalfLife + NuclideProgeny,
+{
+    fn chain_tree_string(&self, root: Nuclide) -> Result<String, Error> {
+        let mut tree = node_label(self, root);
+        tree.push('\n');
+
+        let mut ancestors = vec![root];
+        write_progeny(self, &self.progeny(root)?, "", &mut ancestors, &mut tree);
+
+        Ok(tree)
+    }
+
+    fn build_tree(&self, root: Nuclide) -> Result<ChainTreeNode, Error> {
+        let mut node = ChainTreeNode {
+            nuclide: root,
+            half_life: self.half_life(root).ok(),
+            branch_rate: 1.,
+            decay_mode: DecayModeSet::default(),
+            back_reference: false,
+            children: vec![],
+        };
+
+        let mut ancestors = vec![root];
+        node.children = build_children(self, &self.progeny(root)?, &mut ancestors)?;
+
+        Ok(node)
+    }
+}
+
+fn build_children<T>(
+    data: &T,
+    progeny: &[Progeny],
+    ancestors: &mut Vec<Nuclide>,
+) -> Result<Vec<ChainTreeNode>, Error>
+where
+    T: NuclideHalfLife + NuclideProgeny,
+{
+    let mut children = vec![];
+
+    for daughter in progeny {
+        let nuclide = daughter.nuclide;
+        let half_life = data.half_life(nuclide).ok();
+        let back_reference = ancestors.contains(&nuclide);
+
+        let mut node = ChainTreeNode {
+            nuclide,
+            half_life,
+            branch_rate: daughter.branch_rate,
+            decay_mode: daughter.decay_mode,
+            back_reference,
+            children: vec![],
+        };
+
+        if !back_reference {
+            match data.progeny(nuclide) {
+                Ok(progeny) => {
+                    ancestors.push(nuclide);
+                    node.children = build_children(data, &progeny, ancestors)?;
+                    ancestors.pop();
+                }
+                Err(e) if half_life.is_some_and(|hl| !hl.is_stable()) => return Err(e),
+                Err(_) => {}
+            }
+        }
+
+        children.push(node);
+    }
+
+    Ok(children)
+}
+
+// Declarations of nodes and edges under node, with each nuclide and edge declared once
+fn write_dot(
+    node: &ChainTreeNode,
+    seen: &mut HashSet<(Nuclide, Option<Nuclide>)>,
+    nodes: &mut Vec<String>,
+    edges: &mut Vec<String>,
+) {
+    if seen.insert((node.nuclide, None)) {
+        nodes.push(match node.half_life {
+            Some(half_life) if !half_life.is_stable() => {
+                format!(
+                    "    \"{}\" [label=\"{}\\n{}\"];\n",
+                    node.nuclide, node.nuclide, half_life
+                )
+            }
+            _ => format!("    \"{}\" [shape=box];\n", node.nuclide),
+        });
+    }
+
+    for child in node.children.iter() {
+        if seen.insert((node.nuclide, Some(child.nuclide))) {
+            edges.push(format!(
+                "    \"{}\" -> \"{}\" [label=\"{} {}%\"];\n",
+                node.nuclide,
+                child.nuclide,
+                child.decay_mode,
+                percent(child.branch_rate)
+            ));
+        }
+        if !child.back_reference {
+            write_dot(child, seen, nodes, edges);
+        }
+    }
+}
+
+// Branch rate in percent without trailing zero decimal
+fn percent(branch_rate: f64) -> String {
+    let percent = PrettyPrintFloat(branch_rate * 100.).to_string();
+    percent
+        .strip_suffix(".0")
+        .map_or(percent.clone(), str::to_string)
+}
+
+fn node_label<T: NuclideHalfLife>(data: &T, nuclide: Nuclide) -> String {
+    match data.half_life(nuclide) {
+        Ok(half_life) => format!("{} ({})", nuclide, half_life),
+        Err(_) => nuclide.to_string(),
+    }
+}
+
+fn write_progeny<T>(
+    data: &T,
+    progeny: &[Progeny],
+    prefix: &str,
+    ancestors: &mut Vec<Nuclide>,
+    tree: &mut String,
+) where
+    T: NuclideHalfLife + NuclideProgeny,
+{
+    for (i, daughter) in progeny.iter().enumerate() {
+        let last = i + 1 == progeny.len();
+        let nuclide = daughter.nuclide;
+
+        tree.push_str(&format!(
+            "{}{} {} {}% {}",
+            prefix,
+            if last { "└──" } else { "├──" },
+            daughter.decay_mode,
+            percent(daughter.branch_rate),
+            node_label(data, nuclide)
+        ));
+
+        let child_prefix = format!("{}{}", prefix, if last { "    " } else { "│   " });
+        if ancestors.contains(&nuclide) {
+            tree.push_str(" (cycle)\n");
+        } else if ancestors.len() >= MAX_TREE_DEPTH {
+            tree.push_str(&format!("\n{}└── ...\n", child_prefix));
+        } else {
+            tree.push('\n');
+            if let Ok(progeny) = data.progeny(nuclide) {
+                ancestors.push(nuclide);
+                write_progeny(data, &progeny, &child_prefix, ancestors, tree);
+                ancestors.pop();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::primitive::{DecayMode, DecayModeSet, HalfLife};
+
+    struct TestData;
+
+    impl NuclideHalfLife for TestData {
+        fn half_life(&self, nuclide: Nuclide) -> Result<HalfLife, Error> {
+            if nuclide == "Bi-212".parse().unwrap() {
+                "60.55m".parse()
+            } else if nuclide == "Po-212".parse().unwrap() {
+                "0.299us".parse()
+            } else if nuclide == "Tl-208".parse().unwrap() {
+                "3.053m".parse()
+            } else if nuclide == "U-238".parse().unwrap() {
+                "4.468e9y".parse()
+            } else if nuclide == "Th-234".parse().unwrap() {
+                "24.10d".parse()
+            } else if nuclide == "Pa-234m".parse().unwrap() {
+                "1.159m".parse()
+            } else if nuclide == "Pa-234".parse().unwrap() {
+                "6.70h".parse()
+            } else if nuclide == "U-234".parse().unwrap() {
+                "2.455e5y".parse()
+            } else if nuclide == "Th-230".parse().unwrap() {
+                "7.538e4y".parse()
+            } else if nuclide == "Ra-226".parse().unwrap() {
+                "1600y".parse()
+            } else if nuclide == "Ra-230".parse().unwrap() {
+                "93m".parse()
+            } else {
+                Err(Error::InvalidNuclide(nuclide.to_string()))
+            }
+        }
+    }
+
+    impl NuclideProgeny for TestData {
+        fn progeny(&self, nuclide: Nuclide) -> Result<Vec<Progeny>, Error> {
+            let progeny = |d: &str, branch_rate, mode| Progeny {
+                nuclide: d.parse().unwrap(),
+                branch_rate,
+                decay_mode: DecayModeSet::default() | mode,
+            };
+
+            if nuclide == "Bi-212".parse().unwrap() {
+                Ok(vec![
+                    progeny("Po-212", 0.6406, DecayMode::BetaMinus),
+                    progeny("Tl-208", 0.3594, DecayMode::Alpha),
+                ])
+            } else if nuclide == "Po-212".parse().unwrap() {
+                Ok(vec![progeny("Pb-208", 1., DecayMode::Alpha)])
+            } else if nuclide == "U-238".parse().unwrap() {
+                Ok(vec![progeny("Th-234", 1., DecayMode::Alpha)])
+            } else if nuclide == "Th-234".parse().unwrap() {
+                Ok(vec![progeny("Pa-234m", 1., DecayMode::BetaMinus)])
+            } else if nuclide == "Pa-234m".parse().unwrap() {
+                Ok(vec![
+                    progeny("U-234", 0.9984, DecayMode::BetaMinus),
+                    progeny("Pa-234", 0.0016, DecayMode::IsometricTransition),
+                ])
+            } else if nuclide == "Pa-234".parse().unwrap() {
+                Ok(vec![progeny("U-234", 1., DecayMode::BetaMinus)])
+            } else if nuclide == "U-234".parse().unwrap() {
+                Ok(vec![progeny("Th-230", 1., DecayMode::Alpha)])
+            } else if nuclide == "Th-230".parse().unwrap() {
+                // series truncated
+                Ok(vec![])
+            } else if nuclide == "Ra-230".parse().unwrap() {
+                // Ra-226 has a half-life but no progeny, as if missing in index
+                Ok(vec![progeny("Ra-226", 1., DecayMode::Alpha)])
+            } else if nuclide == "Tl-208".parse().unwrap() {
+                Ok(vec![
+                    progeny("Pb-208", 1., DecayMode::BetaMinus),
+                    // artificial loop back to test cycle detection
+                    progeny("Bi-212", 0., DecayMode::BetaMinus),
+                ])
+            } else {
+                Err(Error::InvalidNuclide(nuclide.to_string()))
+            }
+        }
+    }
+
+    #[test]
+    fn chain_tree_string() {
+        let tree = TestData
+            .chain_tree_string("Bi-212".parse().unwrap())
+            .unwrap();
+        let lines: Vec<&str> = tree.lines().collect();
+
+        assert_eq!(lines.len(), 6);
+        assert_eq!(lines[0], "Bi-212 (60.55 m)");
+        assert_eq!(lines[1], "├── β- 64.06% Po-212 (0.299 μs)");
+        assert_eq!(lines[2], "│   └── ⍺ 100% Pb-208");
+        assert_eq!(lines[3], "└── ⍺ 35.94% Tl-208 (3.053 m)");
+        assert_eq!(lines[4], "    ├── β- 100% Pb-208");
+        assert_eq!(lines[5], "    └── β- 0% Bi-212 (60.55 m) (cycle)");
+    }
+
+    #[test]
+    fn build_tree_with_cycle() {
+        let tree = TestData.build_tree("Bi-212".parse().unwrap()).unwrap();
+
+        assert_eq!(tree.size(), 6);
+        assert_eq!(tree.depth(), 3);
+        assert_eq!(tree.branch_rate, 1.);
+        assert!(tree.half_life.is_some());
+
+        let tl208 = &tree.children[1];
+        assert_eq!(tl208.nuclide, "Tl-208".parse().unwrap());
+        assert_eq!(tl208.branch_rate, 0.3594);
+
+        // stable end product without data is a leaf
+        assert!(tl208.children[0].half_life.is_none());
+        assert!(tl208.children[0].children.is_empty());
+
+        let back = &tl208.children[1];
+        assert_eq!(back.nuclide, "Bi-212".parse().unwrap());
+        assert!(back.back_reference);
+        assert!(back.children.is_empty());
+    }
+
+    #[test]
+    fn build_deep_tree() {
+        let tree = TestData.build_tree("U-238".parse().unwrap()).unwrap();
+
+        // U-234 and Th-230 are reached through both Pa-234m and Pa-234
+        assert_eq!(tree.size(), 8);
+        assert_eq!(tree.depth(), 6);
+
+        let pa234m = &tree.children[0].children[0];
+        assert_eq!(pa234m.nuclide, "Pa-234m".parse().unwrap());
+        assert_eq!(pa234m.children.len(), 2);
+        assert_eq!(pa234m.children[0].branch_rate, 0.9984);
+        assert!(pa234m.children[0]
+            .decay_mode
+            .0
+            .contains(DecayMode::BetaMinus));
+        assert!(!pa234m.children[1].back_reference);
+
+        assert!(matches!(
+            TestData.build_tree("Ra-230".parse().unwrap()),
+            Err(Error::InvalidNuclide(_))
+        ));
+    }
+
+    #[test]
+    fn to_dot() {
+        let dot = TestData.to_dot("U-238".parse().unwrap()).unwrap();
+        let lines: Vec<&str> = dot.lines().collect();
+
+        assert_eq!(lines[0], "digraph \"U-238\" {");
+        assert_eq!(*lines.last().unwrap(), "}");
+        assert!(lines.contains(&r#"    "U-238" [label="U-238\n4.468e9 y"];"#));
+        assert!(lines.contains(&r#"    "Pa-234m" [label="Pa-234m\n1.159 m"];"#));
+        assert!(lines.contains(&r#"    "U-238" -> "Th-234" [label="⍺ 100%"];"#));
+        assert!(lines.contains(&r#"    "Pa-234m" -> "Pa-234" [label="IT 0.16%"];"#));
+
+        // U-234 is reached through two branches but declared once
+        let declared = |lines: &[&str], nuclide| {
+            let prefix = format!("    \"{}\" [", nuclide);
+            lines.iter().filter(|l| l.starts_with(&prefix)).count()
+        };
+        assert_eq!(declared(&lines, "U-234"), 1);
+        assert_eq!(dot.matches(r#""U-234" -> "Th-230""#).count(), 1);
+        assert_eq!(lines.len(), 2 + 6 + 6);
+
+        let dot = TestData.to_dot("Bi-212".parse().unwrap()).unwrap();
+        assert!(dot.contains(r#"    "Pb-208" [shape=box];"#));
+        assert!(dot.contains(r#"    "Tl-208" -> "Bi-212" [label="β- 0%"];"#));
+        let lines: Vec<&str> = dot.lines().collect();
+        assert_eq!(declared(&lines, "Bi-212"), 1);
+    }
+}
@@ -1,6 +1,8 @@
+use chumsky::error::SimpleReason;
 use chumsky::prelude::Simple;
 
 use crate::primitive::attr::Energy;
+use crate::primitive::{Nuclide, Organ, Pathway};
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
@@ -30,21 +32,118 @@ pub enum Error {
     InvalidPathway(String),
     #[error("invalid organ: {0}")]
     InvalidOrgan(String),
+    #[error("no {1} dose coefficient to {2} for {0}")]
+    NoDoseCoefficient(Nuclide, Pathway, Organ),
+    #[error("invalid activity: {0}")]
+    InvalidActivity(String),
+    #[error("invalid concentration: {0}")]
+    InvalidConcentration(String),
+    #[error("invalid yield: {0}")]
+    InvalidYield(String),
+    #[error("invalid decay energy: {0}")]
+    InvalidDecayEnergy(String),
+    #[error("invalid time: {0}")]
+    InvalidTime(String),
+    #[error("invalid fraction: {0}")]
+    InvalidFraction(String),
+    #[error("invalid count rate: {0}")]
+    InvalidCountRate(String),
+    #[error("invalid efficiency: {0}")]
+    InvalidEfficiency(String),
+    #[error("no photon line of {0} at {1} eV")]
+    NoPhotonLine(Nuclide, Energy),
+    #[error("no photon emission of {0}")]
+    NoPhotonEmission(Nuclide),
+    #[error("invalid activity ratio: {0}")]
+    InvalidRatio(String),
+    #[error("activity of {0} chain stays above {1} Bq within {2} s")]
+    ActivityNotReached(Nuclide, f64, f64),
+    #[error("branching fractions of {0} sum to {1}")]
+    InvalidBranching(Nuclide, f64),
+    #[error("{0} is not a daughter of {1}")]
+    NotDaughter(Nuclide, Nuclide),
+    #[error("{0} is not a descendant of {1}")]
+    NotDescendant(Nuclide, Nuclide),
+    #[error("no equilibrium of {1} with {0}")]
+    NoEquilibrium(Nuclide, Nuclide),
+    #[error("{0} and {1} have the same decay constant")]
+    EqualDecayConstants(Nuclide, Nuclide),
+    #[error("parse error: {0}")]
+    Parse(String),
+    #[error("{file}:{line}: {source}")]
+    ParseAt {
+        file: String,
+        line: usize,
+        source: Box<Error>,
+    },
     #[error(transparent)]
     Unexpected(#[from] anyhow::Error),
     #[error(transparent)]
     StdIoError(#[from] std::io::Error),
     #[error("invalid file path")]
     InvalidFilePath,
+    #[error("file not found: {0}")]
+    FileNotFound(std::path::PathBuf),
     #[error("invalid mdb file")]
     InvalidMdbFile,
     #[error(transparent)]
     MdbSqlError(#[from] mdbsql::Error),
 }
 
-// fixme: remove this impl
 impl From<Vec<Simple<char>>> for Error {
-    fn from(e: Vec<Simple<char>>) -> Self {
-        e.into()
+    fn from(errors: Vec<Simple<char>>) -> Self {
+        Self::Parse(
+            errors
+                .iter()
+                .map(describe)
+                .collect::<Vec<String>>()
+                .join("; "),
+        )
+    }
+}
+
+// Diagnostic of parser error with its span, found and expected tokens
+fn describe(e: &Simple<char>) -> String {
+    let found = e
+        .found()
+        .map_or("end of input".to_string(), |c| format!("{:?}", c));
+    let mut expected: Vec<String> = e
+        .expected()
+        .map(|c| c.map_or("end of input".to_string(), |c| format!("{:?}", c)))
+        .collect();
+    expected.sort();
+
+    let mut msg = match e.reason() {
+        SimpleReason::Custom(reason) => format!("{} at {:?}", reason, e.span()),
+        _ => format!("unexpected {} at {:?}", found, e.span()),
+    };
+    if !expected.is_empty() {
+        msg.push_str(&format!(", expected {}", expected.join(", ")));
+    }
+    msg
+}
+
+#[cfg(test)]
+mod test {
+    use chumsky::Error as _;
+
+    use super::*;
+    use crate::primitive::{DecayMode, HalfLife};
+
+    #[test]
+    fn parser_errors() {
+        let e: Error = vec![Simple::expected_input_found(
+            2..3,
+            [Some('a'), None],
+            Some('x'),
+        )]
+        .into();
+        assert_eq!(
+            e.to_string(),
+            "parse error: unexpected 'x' at 2..3, expected 'a', end of input"
+        );
+
+        assert!(matches!("1.5 q".parse::<HalfLife>(), Err(Error::Parse(_))));
+        assert!(matches!("XX".parse::<DecayMode>(), Err(Error::Parse(_))));
     }
 }
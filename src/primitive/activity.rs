@@ -0,0 +1,163 @@
+use std::ops::{Add, Sub};
+use std::str::FromStr;
+
+use float_pretty_print::PrettyPrintFloat;
+
+use crate::error::Error;
+
+/// Becquerels per curie
+const BQ_PER_CI: f64 = 3.7e10;
+
+// SI prefixes and their factors for parsing units
+const PREFIXES: [(&str, f64); 9] = [
+    ("P", 1e15),
+    ("T", 1e12),
+    ("G", 1e9),
+    ("M", 1e6),
+    ("k", 1e3),
+    ("m", 1e-3),
+    ("μ", 1e-6),
+    ("u", 1e-6),
+    ("n", 1e-9),
+];
+
+// Prefixes of unit used for display, from the largest
+const DISPLAY_PREFIXES: [(&str, f64); 6] = [
+    ("P", 1e15),
+    ("T", 1e12),
+    ("G", 1e9),
+    ("M", 1e6),
+    ("k", 1e3),
+    ("", 1.),
+];
+
+/// Activity, stored in becquerels
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Activity(f64);
+
+impl Activity {
+    pub fn from_becquerel(bq: f64) -> Self {
+        Self(bq)
+    }
+
+    pub fn from_curie(ci: f64) -> Self {
+        Self(ci * BQ_PER_CI)
+    }
+
+    pub fn as_becquerel(&self) -> f64 {
+        self.0
+    }
+
+    pub fn as_curie(&self) -> f64 {
+        self.0 / BQ_PER_CI
+    }
+}
+
+impl Add for Activity {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl Sub for Activity {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(self.0 - rhs.0)
+    }
+}
+
+impl FromStr for Activity {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (value, unit) = s
+            .find(|c: char| c.is_alphabetic() && c != 'e' && c != 'E')
+            .map(|i| s.split_at(i))
+            .ok_or_else(|| Error::InvalidActivity(s.to_string()))?;
+
+        let value: f64 = value
+            .trim()
+            .parse()
+            .map_err(|_| Error::InvalidFloat(value.to_string()))?;
+
+        let (prefix, factor) = match unit.strip_suffix("Bq") {
+            Some(prefix) => (prefix, 1.),
+            None => match unit.strip_suffix("Ci") {
+                Some(prefix) => (prefix, BQ_PER_CI),
+                None => return Err(Error::InvalidActivity(s.to_string())),
+            },
+        };
+        let scale = match prefix {
+            "" => 1.,
+            _ => PREFIXES
+                .iter()
+                .find(|(p, _)| *p == prefix)
+                .map(|(_, f)| *f)
+                .ok_or_else(|| Error::InvalidActivity(s.to_string()))?,
+        };
+
+        Ok(Self(value * scale * factor))
+    }
+}
+
+impl std::fmt::Display for Activity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (prefix, factor) = DISPLAY_PREFIXES
+            .into_iter()
+            .find(|(_, factor)| self.0.abs() >= *factor)
+            .unwrap_or(("", 1.));
+
+        let number_str = PrettyPrintFloat(self.0 / factor).to_string();
+        match number_str.strip_suffix(".0") {
+            Some(number_str) => write!(f, "{} {}Bq", number_str, prefix),
+            None => write!(f, "{} {}Bq", number_str, prefix),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn curie_round_trip() {
+        let a = Activity::from_curie(1.);
+        assert_eq!(a.as_becquerel(), 3.7e10);
+        assert_eq!(a.as_curie(), 1.);
+        assert_eq!(Activity::from_becquerel(3.7e7).as_curie(), 1e-3);
+
+        let sum = Activity::from_becquerel(1e3) + Activity::from_becquerel(500.);
+        assert_eq!(sum.as_becquerel(), 1500.);
+        assert_eq!((sum - Activity::from_becquerel(1e3)).as_becquerel(), 500.);
+    }
+
+    #[test]
+    fn activity_from_string() {
+        let a: Activity = "3.7 GBq".parse().unwrap();
+        assert!((a.as_becquerel() - 3.7e9).abs() < 1e-3);
+        assert_eq!(a.to_string(), "3.7 GBq");
+
+        let a: Activity = "1 mCi".parse().unwrap();
+        assert!((a.as_becquerel() - 3.7e7).abs() < 1e-6);
+        assert_eq!(a.to_string(), "37 MBq");
+
+        assert_eq!("250Bq".parse::<Activity>().unwrap().to_string(), "250 Bq");
+        assert_eq!("1e3 Bq".parse::<Activity>().unwrap().to_string(), "1 kBq");
+        assert!(("2 μCi".parse::<Activity>().unwrap().as_becquerel() - 7.4e4).abs() < 1e-6);
+
+        assert!(matches!(
+            "1 mSv".parse::<Activity>(),
+            Err(Error::InvalidActivity(_))
+        ));
+        assert!(matches!(
+            "1 xBq".parse::<Activity>(),
+            Err(Error::InvalidActivity(_))
+        ));
+        assert!("100".parse::<Activity>().is_err());
+        assert!("x Bq".parse::<Activity>().is_err());
+    }
+}
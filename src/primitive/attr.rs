@@ -1,6 +1,6 @@
 use std::collections::BTreeMap;
 
-use super::dose_coefficient::{AgeGroup, DcfValue, Organ};
+use super::dose_coefficient::{AgeGroup, DcfValue, Organ, Pathway};
 use super::notation::{Material, Symbol};
 use super::nuclide::{HalfLife, Nuclide, Progeny};
 use super::DecayModeSet;
@@ -9,13 +9,114 @@ use crate::error::Error;
 /// Energy in eV
 pub type Energy = u32;
 
+/// Comparison of energies within a tolerance
+///
+/// Energies are whole eV rounded from the MeV of data files, so lines of the same value
+/// in a file compare exactly, e.g. as map keys. The same line given by different files
+/// or libraries may still differ by a few eV.
+pub trait EnergyTolerance {
+    /// Whether energies differ by at most `tol_kev` (keV)
+    fn approx_eq(&self, other: &Energy, tol_kev: f64) -> bool;
+
+    /// Energy rounded to the nearest multiple of `kev` (keV), or itself if `kev` is not
+    /// positive
+    fn round_to(&self, kev: f64) -> Energy;
+}
+
+impl EnergyTolerance for Energy {
+    fn approx_eq(&self, other: &Energy, tol_kev: f64) -> bool {
+        (i64::from(*self) - i64::from(*other)).unsigned_abs() as f64 <= tol_kev * 1e3
+    }
+
+    fn round_to(&self, kev: f64) -> Energy {
+        if kev > 0. {
+            let step = kev * 1e3;
+            ((*self as f64 / step).round() * step).round() as Energy
+        } else {
+            *self
+        }
+    }
+}
+
+/// Avogadro constant (mol-1)
+const AVOGADRO_CONSTANT: f64 = 6.022_140_76e23;
+
+/// Tolerance of the sum of branching fractions to unity
+const BRANCHING_TOLERANCE: f64 = 1e-3;
+
 pub trait NuclideProgeny {
     fn progeny(&self, nuclide: Nuclide) -> Result<Vec<Progeny>, Error>;
+
+    /// Branching fraction from parent to its immediate daughter
+    fn branching_fraction(&self, parent: Nuclide, daughter: Nuclide) -> Result<f64, Error> {
+        let branches: Vec<f64> = self
+            .progeny(parent)?
+            .into_iter()
+            .filter(|p| p.nuclide == daughter)
+            .map(|p| p.branch_rate)
+            .collect();
+
+        if branches.is_empty() {
+            Err(Error::NotDaughter(daughter, parent))
+        } else {
+            Ok(branches.iter().sum())
+        }
+    }
+
+    /// Check that branching fractions of all daughters sum to unity
+    ///
+    /// Nuclides without progeny (e.g. stable ones) are always valid.
+    fn validate_branching(&self, parent: Nuclide) -> Result<(), Error> {
+        let progeny = self.progeny(parent)?;
+        if progeny.is_empty() {
+            return Ok(());
+        }
+
+        let total: f64 = progeny.iter().map(|p| p.branch_rate).sum();
+        if (total - 1.).abs() > BRANCHING_TOLERANCE {
+            Err(Error::InvalidBranching(parent, total))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Rate (s-1) of atoms produced into daughter by given parent activity (Bq)
+    fn production_rate_into(
+        &self,
+        parent: Nuclide,
+        daughter: Nuclide,
+        parent_activity: f64,
+    ) -> Result<f64, Error> {
+        Ok(parent_activity * self.branching_fraction(parent, daughter)?)
+    }
 }
 
 pub trait NuclideHalfLife {
     // Half life (arbitrary unit)
     fn half_life(&self, nuclide: Nuclide) -> Result<HalfLife, Error>;
+
+    /// Decay constant (s-1) from half-life, which datasets may cache per nuclide
+    ///
+    /// This backs [`DecayConstant::lambda`] of all half-life data.
+    fn decay_constant(&self, nuclide: Nuclide) -> Result<f64, Error> {
+        self.half_life(nuclide).map(|hl| hl.as_lambda())
+    }
+
+    /// Decay constant (s-1) and its absolute uncertainty propagated from half-life
+    fn lambda_with_uncertainty(&self, nuclide: Nuclide) -> Result<(f64, f64), Error> {
+        self.half_life(nuclide)
+            .map(|hl| hl.as_lambda_with_uncertainty())
+    }
+
+    /// Time of n half-lives, e.g. 10 half-lives until activity is negligible
+    fn decay_time_for_n_half_lives(&self, nuclide: Nuclide, n: f64) -> Result<HalfLife, Error> {
+        let secs = self.half_life(nuclide)?.as_sec();
+        if secs.is_finite() {
+            Ok(HalfLife::from_secs(n * secs))
+        } else {
+            Err(Error::InvalidHalfLife(format!("{} is stable", nuclide)))
+        }
+    }
 }
 
 pub trait NuclideDecayMode {
@@ -25,6 +126,104 @@ pub trait NuclideDecayMode {
 pub trait DecayConstant {
     // Decay constant (s-1)
     fn lambda(&self, nuclide: Nuclide) -> Result<f64, Error>;
+
+    /// Activity ratio of num to den after time t (s), from initial activities (Bq)
+    ///
+    /// Both nuclides decay independently, i.e. neither is produced by the other.
+    fn activity_ratio_over_time(
+        &self,
+        num: Nuclide,
+        den: Nuclide,
+        a0_num: f64,
+        a0_den: f64,
+        t_seconds: f64,
+    ) -> Result<f64, Error> {
+        if !(a0_den > 0. && a0_den.is_finite()) {
+            return Err(Error::InvalidActivity(format!("{} Bq of {}", a0_den, den)));
+        }
+        if !(a0_num >= 0. && a0_num.is_finite()) {
+            return Err(Error::InvalidActivity(format!("{} Bq of {}", a0_num, num)));
+        }
+        if !t_seconds.is_finite() {
+            return Err(Error::InvalidTime(format!("{} s", t_seconds)));
+        }
+        let rate = self.lambda(num)? - self.lambda(den)?;
+
+        Ok(a0_num / a0_den * (-rate * t_seconds).exp())
+    }
+
+    /// Elapsed time (s) for activity ratio of num to den to change from initial ratio to
+    /// measured ratio, the inverse of [`DecayConstant::activity_ratio_over_time`]
+    fn elapsed_from_ratio(
+        &self,
+        num: Nuclide,
+        den: Nuclide,
+        measured_ratio: f64,
+        initial_ratio: f64,
+    ) -> Result<f64, Error> {
+        if let Some(ratio) = [measured_ratio, initial_ratio]
+            .into_iter()
+            .find(|r| !(*r > 0. && r.is_finite()))
+        {
+            return Err(Error::InvalidRatio(ratio.to_string()));
+        }
+        let rate = self.lambda(num)? - self.lambda(den)?;
+        if rate == 0. {
+            return Err(Error::EqualDecayConstants(num, den));
+        }
+
+        Ok((initial_ratio / measured_ratio).ln() / rate)
+    }
+
+    /// Activity (Bq) after time t (s) from initial activity (Bq)
+    fn activity_at(&self, nuclide: Nuclide, a0_bq: f64, t_seconds: f64) -> Result<f64, Error> {
+        let lambda = self.lambda(nuclide)?;
+        if lambda == 0. {
+            Ok(a0_bq)
+        } else {
+            Ok(a0_bq * (-lambda * t_seconds).exp())
+        }
+    }
+
+    /// Activity (Bq) the elapsed time (s) before the measurement, i.e. the measured activity
+    /// corrected for decay
+    fn decay_correct(
+        &self,
+        nuclide: Nuclide,
+        measured_bq: f64,
+        elapsed_seconds: f64,
+    ) -> Result<f64, Error> {
+        self.activity_at(nuclide, measured_bq, -elapsed_seconds)
+    }
+
+    /// Number of decays within `[t0, t1]` (s), the time integral of activity from initial
+    /// activity (Bq) at time 0
+    ///
+    /// This is `a0 / λ (exp(-λ t0) - exp(-λ t1))`, or `a0 (t1 - t0)` for a stable nuclide.
+    /// `t1` may be infinite for all decays after `t0`.
+    fn integrated_decays(
+        &self,
+        nuclide: Nuclide,
+        a0_bq: f64,
+        t0_seconds: f64,
+        t1_seconds: f64,
+    ) -> Result<f64, Error> {
+        let ordered = 0. <= t0_seconds && t0_seconds <= t1_seconds;
+        if !ordered {
+            return Err(Error::InvalidTime(format!(
+                "interval [{}, {}] s",
+                t0_seconds, t1_seconds
+            )));
+        }
+
+        let lambda = self.lambda(nuclide)?;
+        if lambda == 0. {
+            Ok(a0_bq * (t1_seconds - t0_seconds))
+        } else {
+            let remaining = (-lambda * t0_seconds).exp();
+            Ok(-a0_bq * remaining * (-lambda * (t1_seconds - t0_seconds)).exp_m1() / lambda)
+        }
+    }
 }
 
 impl<T> DecayConstant for T
@@ -32,7 +231,57 @@ where
     T: NuclideHalfLife,
 {
     fn lambda(&self, nuclide: Nuclide) -> Result<f64, Error> {
-        self.half_life(nuclide).map(|hl| hl.as_lambda())
+        self.decay_constant(nuclide)
+    }
+}
+
+pub trait NuclideMass {
+    /// Atomic mass of nuclide (amu)
+    fn nuclide_mass(&self, nuclide: Nuclide) -> Result<f64, Error>;
+}
+
+pub trait SpecificActivity {
+    /// Specific activity (Bq/g), which is zero for stable nuclides
+    ///
+    /// This is `λ N_A / M` with the atomic mass M of the dataset, e.g. AMU of ICRP-07 index.
+    fn specific_activity(&self, nuclide: Nuclide) -> Result<f64, Error>;
+
+    /// Activity (Bq) of given mass (g) of nuclide
+    fn mass_to_activity(&self, nuclide: Nuclide, mass: f64) -> Result<f64, Error> {
+        Ok(self.specific_activity(nuclide)? * mass)
+    }
+
+    /// Mass (g) of nuclide with given activity (Bq), which fails for stable nuclides
+    fn activity_to_mass(&self, nuclide: Nuclide, activity: f64) -> Result<f64, Error> {
+        match self.specific_activity(nuclide)? {
+            a if a > 0. => Ok(activity / a),
+            _ => Err(Error::InvalidHalfLife(format!("{} is stable", nuclide))),
+        }
+    }
+
+    /// Total activity (Bq) of a sample given as (nuclide, mass in g) components
+    ///
+    /// Components without a known half-life (e.g. stable nuclides absent in the dataset)
+    /// are errors rather than silently skipped, so a misspelled nuclide is not lost.
+    fn sample_activity(&self, components: &[(Nuclide, f64)]) -> Result<f64, Error> {
+        components
+            .iter()
+            .map(|&(nuclide, mass)| self.mass_to_activity(nuclide, mass))
+            .sum()
+    }
+}
+
+impl<T> SpecificActivity for T
+where
+    T: DecayConstant + NuclideMass,
+{
+    fn specific_activity(&self, nuclide: Nuclide) -> Result<f64, Error> {
+        let lambda = self.lambda(nuclide)?;
+        if lambda == 0. {
+            Ok(0.)
+        } else {
+            Ok(lambda * AVOGADRO_CONSTANT / self.nuclide_mass(nuclide)?)
+        }
     }
 }
 
@@ -55,6 +304,25 @@ pub trait MassAttenuationCoefficient {
     ) -> Result<f64, Error>;
 }
 
+/// Source of mass attenuation coefficients for shielding calculations
+///
+/// Any [`MassAttenuationCoefficient`] dataset (e.g. `NistMassAttenCoef`) provides this,
+/// and other tables such as NIST XCOM may be supplied by implementing it directly.
+pub trait AttenuationData {
+    /// Mass attenuation coefficient (cm2/g) of material at energy (MeV), if tabulated
+    fn mass_attenuation(&self, material: &Material, energy_mev: f64) -> Option<f64>;
+}
+
+impl<T> AttenuationData for T
+where
+    T: MassAttenuationCoefficient,
+{
+    fn mass_attenuation(&self, material: &Material, energy_mev: f64) -> Option<f64> {
+        self.mass_attenuation_coefficient(material, (energy_mev * 1e6).round() as Energy)
+            .ok()
+    }
+}
+
 pub trait MeanFreePath {
     /// Mean free path (cm)
     fn mfp(&self, material: &Material, energy: Energy) -> Result<f64, Error>;
@@ -162,3 +430,504 @@ pub trait DcfInhalation {
         organ: Organ,
     ) -> Result<Vec<DcfValue>, Error>;
 }
+
+pub trait IntakeDose {
+    /// Committed dose coefficient (Sv/Bq) to organ of ingestion or inhalation
+    ///
+    /// The largest coefficient is taken if several chemical forms or absorption types
+    /// are tabulated.
+    fn dose_coefficient(
+        &self,
+        nuclide: Nuclide,
+        pathway: Pathway,
+        age_group: AgeGroup,
+        organ: Organ,
+    ) -> Result<f64, Error>;
+
+    /// Committed effective dose coefficient (Sv/Bq) of ingestion or inhalation
+    fn intake_dose_coefficient(
+        &self,
+        nuclide: Nuclide,
+        pathway: Pathway,
+        age_group: AgeGroup,
+    ) -> Result<f64, Error> {
+        self.dose_coefficient(nuclide, pathway, age_group, Organ::EffectiveDose)
+    }
+
+    /// Committed effective dose (Sv) of constant intake rate (Bq/s) over duration (s)
+    ///
+    /// Total intake is multiplied by the dose coefficient of a single intake, which
+    /// neglects aging of the receptor during intake period.
+    fn chronic_intake_dose(
+        &self,
+        nuclide: Nuclide,
+        intake_rate: f64,
+        duration: f64,
+        pathway: Pathway,
+        age_group: AgeGroup,
+    ) -> Result<f64, Error> {
+        Ok(intake_rate * duration * self.intake_dose_coefficient(nuclide, pathway, age_group)?)
+    }
+}
+
+impl<T> IntakeDose for T
+where
+    T: DcfIngestion + DcfInhalation,
+{
+    fn dose_coefficient(
+        &self,
+        nuclide: Nuclide,
+        pathway: Pathway,
+        age_group: AgeGroup,
+        organ: Organ,
+    ) -> Result<f64, Error> {
+        let values = match pathway {
+            Pathway::Ingestion => self.dcf_ingestion(nuclide, age_group, organ)?,
+            Pathway::Inhalation => self.dcf_inhalation(nuclide, age_group, organ)?,
+            _ => return Err(Error::InvalidPathway(pathway.to_string())),
+        };
+
+        values
+            .into_iter()
+            .map(|dcf| dcf.value)
+            .reduce(f64::max)
+            .ok_or(Error::NoDoseCoefficient(nuclide, pathway, organ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::primitive::TimeUnit;
+
+    struct TestData;
+
+    impl NuclideHalfLife for TestData {
+        fn half_life(&self, nuclide: Nuclide) -> Result<HalfLife, Error> {
+            if nuclide == "Cs-137".parse().unwrap() {
+                "30.08(9) y".parse()
+            } else if nuclide == "Cs-134".parse().unwrap() {
+                "2.0652 y".parse()
+            } else if nuclide == "Co-60".parse().unwrap() {
+                "5.2713 y".parse()
+            } else if nuclide == "Ba-137".parse().unwrap() {
+                Ok(HalfLife::stable())
+            } else {
+                Err(Error::InvalidNuclide(nuclide.to_string()))
+            }
+        }
+    }
+
+    impl NuclideMass for TestData {
+        fn nuclide_mass(&self, nuclide: Nuclide) -> Result<f64, Error> {
+            if nuclide == "Cs-137".parse().unwrap() {
+                Ok(136.907089)
+            } else if nuclide == "Co-60".parse().unwrap() {
+                Ok(59.933817)
+            } else {
+                Err(Error::InvalidNuclide(nuclide.to_string()))
+            }
+        }
+    }
+
+    #[test]
+    fn energy_tolerance() {
+        let (a, b): (Energy, Energy) = (661_650, 661_700);
+        assert!(a.approx_eq(&b, 0.1));
+        assert!(b.approx_eq(&a, 0.1));
+        assert!(!a.approx_eq(&b, 0.01));
+        assert!(a.approx_eq(&a, 0.));
+
+        assert_eq!(a.round_to(1.), 662_000);
+        assert_eq!(661_400u32.round_to(1.), 661_000);
+        assert_eq!(a.round_to(0.1), 661_700);
+        assert_eq!(31_817u32.round_to(0.5), 32_000);
+        assert_eq!(a.round_to(0.), a);
+    }
+
+    #[test]
+    fn specific_activity() {
+        let data = TestData;
+
+        let cs137 = data.specific_activity("Cs-137".parse().unwrap()).unwrap();
+        assert!((cs137 / 3.2e12 - 1.).abs() < 0.01);
+
+        let co60 = data.specific_activity("Co-60".parse().unwrap()).unwrap();
+        assert!(co60 > cs137);
+        assert!((co60 / 4.18e13 - 1.).abs() < 0.01);
+
+        // no atomic mass needed for stable nuclides
+        let ba137: Nuclide = "Ba-137".parse().unwrap();
+        assert_eq!(data.specific_activity(ba137).unwrap(), 0.);
+        assert_eq!(data.mass_to_activity(ba137, 1.).unwrap(), 0.);
+        assert!(matches!(
+            data.activity_to_mass(ba137, 1.),
+            Err(Error::InvalidHalfLife(_))
+        ));
+    }
+
+    #[test]
+    fn activity_to_mass() {
+        let data = TestData;
+        let co60: Nuclide = "Co-60".parse().unwrap();
+
+        let mass = data.activity_to_mass(co60, 3.7e10).unwrap();
+        assert!((mass / 8.85e-4 - 1.).abs() < 0.01);
+        assert!((data.mass_to_activity(co60, mass).unwrap() / 3.7e10 - 1.).abs() < 1e-12);
+        assert!(matches!(
+            data.activity_to_mass("H-3".parse().unwrap(), 1.),
+            Err(Error::InvalidNuclide(_))
+        ));
+    }
+
+    impl NuclideProgeny for TestData {
+        fn progeny(&self, nuclide: Nuclide) -> Result<Vec<Progeny>, Error> {
+            let progeny = |d: &str, branch_rate| Progeny {
+                nuclide: d.parse().unwrap(),
+                branch_rate,
+                decay_mode: DecayModeSet::default(),
+            };
+
+            if nuclide == "Bi-212".parse().unwrap() {
+                Ok(vec![progeny("Po-212", 0.6406), progeny("Tl-208", 0.3594)])
+            } else if nuclide == "Cu-64".parse().unwrap() {
+                Ok(vec![progeny("Ni-64", 0.61), progeny("Zn-64", 0.39)])
+            } else if nuclide == "Ag-110m".parse().unwrap() {
+                // corrupted entry without its IT branch to Ag-110
+                Ok(vec![progeny("Cd-110", 0.9867)])
+            } else if nuclide == "Ba-137".parse().unwrap() {
+                Ok(vec![])
+            } else {
+                Err(Error::InvalidNuclide(nuclide.to_string()))
+            }
+        }
+    }
+
+    #[test]
+    fn production_rate_into() {
+        let data = TestData;
+        let bi212: Nuclide = "Bi-212".parse().unwrap();
+
+        let rate = data
+            .production_rate_into(bi212, "Tl-208".parse().unwrap(), 1000.)
+            .unwrap();
+        assert!((rate - 359.4).abs() < 1e-9);
+
+        let rate = data
+            .production_rate_into(bi212, "Po-212".parse().unwrap(), 1000.)
+            .unwrap();
+        assert!((rate - 640.6).abs() < 1e-9);
+
+        assert!(data
+            .production_rate_into(bi212, "Pb-208".parse().unwrap(), 1000.)
+            .is_err());
+    }
+
+    #[test]
+    fn branching_fraction() {
+        let data = TestData;
+        let cu64: Nuclide = "Cu-64".parse().unwrap();
+
+        let ni64 = data
+            .branching_fraction(cu64, "Ni-64".parse().unwrap())
+            .unwrap();
+        let zn64 = data
+            .branching_fraction(cu64, "Zn-64".parse().unwrap())
+            .unwrap();
+        assert_eq!(ni64, 0.61);
+        assert!((ni64 + zn64 - 1.).abs() < 1e-12);
+        assert!(matches!(
+            data.branching_fraction(cu64, "Cu-63".parse().unwrap()),
+            Err(Error::NotDaughter(..))
+        ));
+
+        assert!(data.validate_branching(cu64).is_ok());
+        assert!(data.validate_branching("Bi-212".parse().unwrap()).is_ok());
+        assert!(data.validate_branching("Ba-137".parse().unwrap()).is_ok());
+        assert!(matches!(
+            data.validate_branching("Ag-110m".parse().unwrap()),
+            Err(Error::InvalidBranching(..))
+        ));
+        assert!(data.validate_branching("H-3".parse().unwrap()).is_err());
+    }
+
+    #[test]
+    fn sample_activity() {
+        let data = TestData;
+        let cs137: Nuclide = "Cs-137".parse().unwrap();
+        let co60: Nuclide = "Co-60".parse().unwrap();
+
+        let activity = data
+            .sample_activity(&[(cs137, 1e-3), (co60, 2e-3)])
+            .unwrap();
+        let expected = data.specific_activity(cs137).unwrap() * 1e-3
+            + data.specific_activity(co60).unwrap() * 2e-3;
+        assert!((activity - expected).abs() / expected < 1e-12);
+
+        assert_eq!(data.sample_activity(&[]).unwrap(), 0.);
+        assert!(data
+            .sample_activity(&[(cs137, 1.), ("H-3".parse().unwrap(), 1.)])
+            .is_err());
+    }
+
+    impl DcfIngestion for TestData {
+        fn dcf_ingestion(
+            &self,
+            nuclide: Nuclide,
+            age_group: AgeGroup,
+            organ: Organ,
+        ) -> Result<Vec<DcfValue>, Error> {
+            let value = |value| {
+                Ok(vec![DcfValue {
+                    value,
+                    unit: "Sv/Bq".to_string(),
+                    attr: None,
+                }])
+            };
+            match (age_group, organ) {
+                _ if nuclide != "Cs-137".parse().unwrap() => Ok(vec![]),
+                (AgeGroup::Adult, Organ::EffectiveDose) => value(1.3e-8),
+                (AgeGroup::Adult, Organ::Liver) => value(1.4e-8),
+                (AgeGroup::ThreeMonth, Organ::EffectiveDose) => value(2.1e-8),
+                _ => Ok(vec![]),
+            }
+        }
+    }
+
+    impl DcfInhalation for TestData {
+        fn dcf_inhalation(
+            &self,
+            _nuclide: Nuclide,
+            _age_group: AgeGroup,
+            _organ: Organ,
+        ) -> Result<Vec<DcfValue>, Error> {
+            Ok(vec![])
+        }
+    }
+
+    #[test]
+    fn chronic_intake_dose() {
+        let data = TestData;
+        let cs137: Nuclide = "Cs-137".parse().unwrap();
+        let year = 365.2422 * 86_400.;
+
+        let dose = data
+            .chronic_intake_dose(cs137, 1e-3, year, Pathway::Ingestion, AgeGroup::Adult)
+            .unwrap();
+        assert!((dose - 1e-3 * year * 1.3e-8).abs() / dose < 1e-12);
+
+        assert!(data
+            .chronic_intake_dose(cs137, 1e-3, year, Pathway::Inhalation, AgeGroup::Adult)
+            .is_err());
+        assert!(matches!(
+            data.chronic_intake_dose(cs137, 1e-3, year, Pathway::AirSubmersion, AgeGroup::Adult),
+            Err(Error::InvalidPathway(_))
+        ));
+    }
+
+    #[test]
+    fn dose_coefficient() {
+        let data = TestData;
+        let cs137: Nuclide = "Cs-137".parse().unwrap();
+
+        // ICRP 72 ingestion coefficients of Cs-137
+        let dcf = |age, organ| data.dose_coefficient(cs137, Pathway::Ingestion, age, organ);
+        assert_eq!(dcf(AgeGroup::Adult, Organ::EffectiveDose).unwrap(), 1.3e-8);
+        assert_eq!(
+            dcf(AgeGroup::ThreeMonth, Organ::EffectiveDose).unwrap(),
+            2.1e-8
+        );
+        assert_eq!(dcf(AgeGroup::Adult, Organ::Liver).unwrap(), 1.4e-8);
+        assert!(matches!(
+            dcf(AgeGroup::Adult, Organ::Thyroid),
+            Err(Error::NoDoseCoefficient(n, Pathway::Ingestion, Organ::Thyroid)) if n == cs137
+        ));
+        assert_eq!(
+            data.intake_dose_coefficient(cs137, Pathway::Ingestion, AgeGroup::Adult)
+                .unwrap(),
+            1.3e-8
+        );
+
+        assert!(matches!(
+            data.dose_coefficient(cs137, Pathway::GroundSurface, AgeGroup::Adult, Organ::Liver),
+            Err(Error::InvalidPathway(_))
+        ));
+        assert!(matches!("Adult".parse::<AgeGroup>(), Ok(AgeGroup::Adult)));
+        assert!(matches!(
+            "2 yr-old".parse::<AgeGroup>(),
+            Err(Error::InvalidAgeGroup(_))
+        ));
+        assert!(matches!("Liver".parse::<Organ>(), Ok(Organ::Liver)));
+        assert!(matches!(
+            "Heart".parse::<Organ>(),
+            Err(Error::InvalidOrgan(_))
+        ));
+        assert!(matches!(
+            "Injection".parse::<Pathway>(),
+            Err(Error::InvalidPathway(_))
+        ));
+    }
+
+    #[test]
+    fn decay_time_for_n_half_lives() {
+        let data = TestData;
+
+        let t = data
+            .decay_time_for_n_half_lives("Co-60".parse().unwrap(), 10.)
+            .unwrap();
+        assert_eq!(t.unit, TimeUnit::Year);
+        assert!((t.value - 52.713).abs() < 1e-9);
+
+        assert!(data
+            .decay_time_for_n_half_lives("H-3".parse().unwrap(), 10.)
+            .is_err());
+    }
+
+    #[test]
+    fn lambda_with_uncertainty() {
+        let data = TestData;
+
+        let (lambda, sigma) = data
+            .lambda_with_uncertainty("Cs-137".parse().unwrap())
+            .unwrap();
+        let expected = 2.0_f64.ln() / (30.08 * 365.2422 * 86400.);
+        assert!((lambda - expected).abs() / expected < 1e-12);
+        assert!((sigma / lambda - 0.09 / 30.08).abs() < 1e-12);
+
+        let (_, sigma) = data
+            .lambda_with_uncertainty("Co-60".parse().unwrap())
+            .unwrap();
+        assert_eq!(sigma, 0.);
+
+        assert!(data
+            .lambda_with_uncertainty("H-3".parse().unwrap())
+            .is_err());
+    }
+
+    #[test]
+    fn activity_ratio_over_time() {
+        let data = TestData;
+        let cs134: Nuclide = "Cs-134".parse().unwrap();
+        let cs137: Nuclide = "Cs-137".parse().unwrap();
+        let year = 365.2422 * 86_400.;
+
+        let ratio = |t| {
+            data.activity_ratio_over_time(cs134, cs137, 1., 1., t)
+                .unwrap()
+        };
+        assert_eq!(ratio(0.), 1.);
+        assert!(ratio(year) < 1.);
+        assert!(ratio(5. * year) < ratio(year));
+
+        let expected = (2.0_f64.ln() * (1. / 30.08 - 1. / 2.0652) * 5.).exp();
+        assert!((ratio(5. * year) - expected).abs() / expected < 1e-12);
+
+        assert!(matches!(
+            data.activity_ratio_over_time(cs134, cs137, 1., 0., year),
+            Err(Error::InvalidActivity(_))
+        ));
+        assert!(matches!(
+            data.activity_ratio_over_time(cs134, cs137, -1., 1., year),
+            Err(Error::InvalidActivity(_))
+        ));
+        assert!(matches!(
+            data.activity_ratio_over_time(cs134, cs137, 1., 1., f64::NAN),
+            Err(Error::InvalidTime(_))
+        ));
+        assert!(data
+            .activity_ratio_over_time(cs134, "H-3".parse().unwrap(), 1., 1., year)
+            .is_err());
+    }
+
+    #[test]
+    fn activity_at() {
+        let data = TestData;
+        let cs137: Nuclide = "Cs-137".parse().unwrap();
+        let ten_years = 10. * 365.2422 * 86_400.;
+
+        let a = data.activity_at(cs137, 1000., ten_years).unwrap();
+        assert!((a - 1000. * 0.5f64.powf(10. / 30.08)).abs() < 1e-9);
+        assert!((a / 1000. - 0.794).abs() < 1e-3);
+
+        let a0 = data.decay_correct(cs137, a, ten_years).unwrap();
+        assert!((a0 - 1000.).abs() < 1e-9);
+        assert_eq!(data.activity_at(cs137, 1000., 0.).unwrap(), 1000.);
+
+        let ba137: Nuclide = "Ba-137".parse().unwrap();
+        assert_eq!(
+            data.activity_at(ba137, 1000., f64::INFINITY).unwrap(),
+            1000.
+        );
+        assert_eq!(
+            data.decay_correct(ba137, 1000., f64::INFINITY).unwrap(),
+            1000.
+        );
+
+        assert!(data.activity_at("H-3".parse().unwrap(), 1000., 1.).is_err());
+    }
+
+    #[test]
+    fn integrated_decays() {
+        let data = TestData;
+        let co60: Nuclide = "Co-60".parse().unwrap();
+        let lambda = data.lambda(co60).unwrap();
+
+        let all = data
+            .integrated_decays(co60, 1000., 0., f64::INFINITY)
+            .unwrap();
+        assert!((all - 1000. / lambda).abs() / all < 1e-12);
+
+        let year = 365.2422 * 86_400.;
+        let first = data.integrated_decays(co60, 1000., 0., year).unwrap();
+        let rest = data
+            .integrated_decays(co60, 1000., year, f64::INFINITY)
+            .unwrap();
+        assert!((first + rest - all).abs() / all < 1e-12);
+        let expected = 1000. / lambda * (1. - (-lambda * year).exp());
+        assert!((first - expected).abs() / expected < 1e-12);
+        assert_eq!(data.integrated_decays(co60, 1000., year, year).unwrap(), 0.);
+
+        let ba137: Nuclide = "Ba-137".parse().unwrap();
+        assert_eq!(
+            data.integrated_decays(ba137, 1000., 10., 70.).unwrap(),
+            60_000.
+        );
+
+        for (t0, t1) in [(70., 10.), (-1., 10.), (f64::NAN, 10.)] {
+            assert!(matches!(
+                data.integrated_decays(co60, 1000., t0, t1),
+                Err(Error::InvalidTime(_))
+            ));
+        }
+        assert!(data
+            .integrated_decays("H-3".parse().unwrap(), 1000., 0., 1.)
+            .is_err());
+    }
+
+    #[test]
+    fn elapsed_from_ratio() {
+        let data = TestData;
+        let cs134: Nuclide = "Cs-134".parse().unwrap();
+        let cs137: Nuclide = "Cs-137".parse().unwrap();
+        let t = 3.5 * 365.2422 * 86_400.;
+
+        let ratio = data
+            .activity_ratio_over_time(cs134, cs137, 0.9, 1., t)
+            .unwrap();
+        let elapsed = data.elapsed_from_ratio(cs134, cs137, ratio, 0.9).unwrap();
+        assert!((elapsed - t).abs() / t < 1e-9);
+
+        assert!(matches!(
+            data.elapsed_from_ratio(cs137, cs137, 0.5, 1.),
+            Err(Error::EqualDecayConstants(..))
+        ));
+        assert!(matches!(
+            data.elapsed_from_ratio(cs134, cs137, 0., 1.),
+            Err(Error::InvalidRatio(_))
+        ));
+        assert!(matches!(
+            data.elapsed_from_ratio(cs134, cs137, 0.5, f64::INFINITY),
+            Err(Error::InvalidRatio(_))
+        ));
+    }
+}
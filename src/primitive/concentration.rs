@@ -0,0 +1,147 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ConcentrationUnit {
+    #[serde(rename = "Bq/L")]
+    BqPerLiter,
+    #[serde(rename = "Bq/kg")]
+    BqPerKilogram,
+    #[serde(rename = "Bq/m3", alias = "Bq/m³")]
+    BqPerCubicMeter,
+}
+
+serde_plain::derive_display_from_serialize!(ConcentrationUnit);
+serde_plain::derive_fromstr_from_deserialize!(ConcentrationUnit, |e| -> Error {
+    Error::InvalidConcentration(e.to_string())
+});
+
+impl ConcentrationUnit {
+    // Liters per unit of amount for volumetric units
+    fn liters(&self) -> Option<f64> {
+        match self {
+            Self::BqPerLiter => Some(1.),
+            Self::BqPerKilogram => None,
+            Self::BqPerCubicMeter => Some(1e3),
+        }
+    }
+}
+
+/// Activity concentration, e.g. `12.5 Bq/L`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Concentration {
+    pub value: f64,
+    pub unit: ConcentrationUnit,
+}
+
+impl Concentration {
+    pub fn new(value: f64, unit: ConcentrationUnit) -> Self {
+        Self { value, unit }
+    }
+
+    /// Concentration in another unit
+    ///
+    /// Conversion between mass and volume basis needs density (kg/L) of the medium.
+    pub fn convert(
+        &self,
+        unit: ConcentrationUnit,
+        density: Option<f64>,
+    ) -> Result<Concentration, Error> {
+        // Activity per liter, or per kilogram for mass basis
+        let value = match (self.unit.liters(), unit.liters()) {
+            (Some(from), Some(to)) => self.value / from * to,
+            (None, None) => self.value,
+            (Some(from), None) => self.value / from / density_of(density)?,
+            (None, Some(to)) => self.value * density_of(density)? * to,
+        };
+
+        Ok(Self { value, unit })
+    }
+
+    /// Total activity (Bq) of given amount of medium (L, kg or m3 as the unit)
+    pub fn total_activity(&self, amount: f64) -> f64 {
+        self.value * amount
+    }
+}
+
+fn density_of(density: Option<f64>) -> Result<f64, Error> {
+    match density {
+        Some(d) if d > 0. => Ok(d),
+        _ => Err(Error::InvalidConcentration(
+            "density is required between mass and volume basis".to_string(),
+        )),
+    }
+}
+
+impl FromStr for Concentration {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (value, unit) = s
+            .find("Bq")
+            .map(|i| s.split_at(i))
+            .ok_or_else(|| Error::InvalidConcentration(s.to_string()))?;
+
+        Ok(Self {
+            value: value
+                .trim()
+                .parse()
+                .map_err(|_| Error::InvalidFloat(value.to_string()))?,
+            unit: unit.parse()?,
+        })
+    }
+}
+
+impl std::fmt::Display for Concentration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.value, self.unit)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn concentration_from_string() {
+        let c: Concentration = "12.5 Bq/L".parse().unwrap();
+        assert_eq!(c, Concentration::new(12.5, ConcentrationUnit::BqPerLiter));
+        assert_eq!(c.to_string(), "12.5 Bq/L");
+        assert_eq!(c.to_string().parse::<Concentration>().unwrap(), c);
+
+        let c: Concentration = "3e2Bq/m³".parse().unwrap();
+        assert_eq!(c.unit, ConcentrationUnit::BqPerCubicMeter);
+        assert_eq!(c.to_string(), "300 Bq/m3");
+
+        assert!("1.0 Bq/g".parse::<Concentration>().is_err());
+        assert!("x Bq/kg".parse::<Concentration>().is_err());
+    }
+
+    #[test]
+    fn concentration_conversion() {
+        let c = Concentration::new(2., ConcentrationUnit::BqPerLiter);
+
+        let c_m3 = c.convert(ConcentrationUnit::BqPerCubicMeter, None).unwrap();
+        assert_eq!(c_m3.value, 2000.);
+        assert_eq!(
+            c_m3.convert(ConcentrationUnit::BqPerLiter, None).unwrap(),
+            c
+        );
+
+        assert!(c.convert(ConcentrationUnit::BqPerKilogram, None).is_err());
+        let c_kg = c
+            .convert(ConcentrationUnit::BqPerKilogram, Some(0.8))
+            .unwrap();
+        assert_eq!(c_kg.value, 2.5);
+        let c_l = c_kg
+            .convert(ConcentrationUnit::BqPerLiter, Some(0.8))
+            .unwrap();
+        assert_eq!(c_l.value, 2.);
+
+        assert_eq!(c_m3.total_activity(0.5), 1000.);
+    }
+}
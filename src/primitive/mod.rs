@@ -1,16 +1,31 @@
+pub mod activity;
 pub mod attr;
+pub mod concentration;
 pub mod dose_coefficient;
 pub mod notation;
 pub mod nuclide;
 pub mod parser;
+#[cfg(feature = "chrono")]
+pub mod source;
 
+pub use activity::Activity;
 pub use attr::{
-    AtomicMass, DcfAirSubmersion, DcfGroundSurface, DcfIngestion, DcfInhalation, DcfSoilFifteenCm,
-    DcfSoilFiveCm, DcfSoilInfinite, DcfSoilOneCm, DcfWaterImmersion, DecayConstant,
-    MassAttenuationCoefficient, NuclideDecayMode, NuclideHalfLife, NuclideProgeny,
+    AtomicMass, AttenuationData, DcfAirSubmersion, DcfGroundSurface, DcfIngestion, DcfInhalation,
+    DcfSoilFifteenCm, DcfSoilFiveCm, DcfSoilInfinite, DcfSoilOneCm, DcfWaterImmersion,
+    DecayConstant, EnergyTolerance, IntakeDose, MassAttenuationCoefficient, NuclideDecayMode,
+    NuclideHalfLife, NuclideMass, NuclideProgeny, SpecificActivity,
 };
+pub use concentration::{Concentration, ConcentrationUnit};
 pub use dose_coefficient::{
     AgeGroup, BiokineticAttr, ClearanceClass, DcfValue, Organ, Pathway, PulmonaryAbsorptionType,
 };
-pub use notation::{Material, MaterialBuilder, Symbol};
-pub use nuclide::{DecayMode, DecayModeSet, HalfLife, Nuclide, Progeny, TimeUnit};
+pub use notation::{
+    atomic_number, atomic_number_by_name, element_symbol, Material, MaterialBuilder, Symbol,
+    ELEMENT_NAMES, ELEMENT_SYMBOLS,
+};
+pub use nuclide::{
+    half_life_from_lambda, lambda_from_half_life, DecayMode, DecayModeSet, HalfLife, Nuclide,
+    Progeny, TimeUnit, YearConvention,
+};
+#[cfg(feature = "chrono")]
+pub use source::Source;
@@ -25,6 +25,70 @@ pub enum Symbol {
     Lv, Ts, Og,
 }
 
+/// Element symbols ordered by atomic number
+#[rustfmt::skip]
+pub const ELEMENT_SYMBOLS: [&str; 118] = [
+    "H", "He", "Li", "Be", "B", "C", "N", "O", "F", "Ne", "Na", "Mg", "Al", "Si", "P", "S",
+    "Cl", "Ar", "K", "Ca", "Sc", "Ti", "V", "Cr", "Mn", "Fe", "Co", "Ni", "Cu", "Zn", "Ga",
+    "Ge", "As", "Se", "Br", "Kr", "Rb", "Sr", "Y", "Zr", "Nb", "Mo", "Tc", "Ru", "Rh", "Pd",
+    "Ag", "Cd", "In", "Sn", "Sb", "Te", "I", "Xe", "Cs", "Ba", "La", "Ce", "Pr", "Nd", "Pm",
+    "Sm", "Eu", "Gd", "Tb", "Dy", "Ho", "Er", "Tm", "Yb", "Lu", "Hf", "Ta", "W", "Re", "Os",
+    "Ir", "Pt", "Au", "Hg", "Tl", "Pb", "Bi", "Po", "At", "Rn", "Fr", "Ra", "Ac", "Th", "Pa",
+    "U", "Np", "Pu", "Am", "Cm", "Bk", "Cf", "Es", "Fm", "Md", "No", "Lr", "Rf", "Db", "Sg",
+    "Bh", "Hs", "Mt", "Ds", "Rg", "Cn", "Nh", "Fl", "Mc", "Lv", "Ts", "Og",
+];
+
+/// Element names ordered by atomic number
+#[rustfmt::skip]
+pub const ELEMENT_NAMES: [&str; 118] = [
+    "Hydrogen", "Helium", "Lithium", "Beryllium", "Boron", "Carbon", "Nitrogen", "Oxygen",
+    "Fluorine", "Neon", "Sodium", "Magnesium", "Aluminium", "Silicon", "Phosphorus", "Sulfur",
+    "Chlorine", "Argon", "Potassium", "Calcium", "Scandium", "Titanium", "Vanadium", "Chromium",
+    "Manganese", "Iron", "Cobalt", "Nickel", "Copper", "Zinc", "Gallium", "Germanium", "Arsenic",
+    "Selenium", "Bromine", "Krypton", "Rubidium", "Strontium", "Yttrium", "Zirconium", "Niobium",
+    "Molybdenum", "Technetium", "Ruthenium", "Rhodium", "Palladium", "Silver", "Cadmium",
+    "Indium", "Tin", "Antimony", "Tellurium", "Iodine", "Xenon", "Caesium", "Barium",
+    "Lanthanum", "Cerium", "Praseodymium", "Neodymium", "Promethium", "Samarium", "Europium",
+    "Gadolinium", "Terbium", "Dysprosium", "Holmium", "Erbium", "Thulium", "Ytterbium",
+    "Lutetium", "Hafnium", "Tantalum", "Tungsten", "Rhenium", "Osmium", "Iridium", "Platinum",
+    "Gold", "Mercury", "Thallium", "Lead", "Bismuth", "Polonium", "Astatine", "Radon",
+    "Francium", "Radium", "Actinium", "Thorium", "Protactinium", "Uranium", "Neptunium",
+    "Plutonium", "Americium", "Curium", "Berkelium", "Californium", "Einsteinium", "Fermium",
+    "Mendelevium", "Nobelium", "Lawrencium", "Rutherfordium", "Dubnium", "Seaborgium",
+    "Bohrium", "Hassium", "Meitnerium", "Darmstadtium", "Roentgenium", "Copernicium",
+    "Nihonium", "Flerovium", "Moscovium", "Livermorium", "Tennessine", "Oganesson",
+];
+
+/// Element symbol of atomic number
+pub fn element_symbol(z: u8) -> Option<&'static str> {
+    ELEMENT_SYMBOLS.get(usize::from(z).checked_sub(1)?).copied()
+}
+
+/// Atomic number of element symbol (case sensitive)
+pub fn atomic_number(symbol: &str) -> Option<u8> {
+    ELEMENT_SYMBOLS
+        .iter()
+        .position(|&s| s == symbol)
+        .map(|i| i as u8 + 1)
+}
+
+/// Atomic number of element name (case insensitive), e.g. "cobalt"
+///
+/// Alternative spellings "Aluminum", "Cesium" and "Sulphur" are accepted as well.
+pub fn atomic_number_by_name(name: &str) -> Option<u8> {
+    let name = name.to_ascii_lowercase();
+    let name = match name.as_str() {
+        "aluminum" => "aluminium",
+        "cesium" => "caesium",
+        "sulphur" => "sulfur",
+        s => s,
+    };
+    ELEMENT_NAMES
+        .iter()
+        .position(|n| n.eq_ignore_ascii_case(name))
+        .map(|i| i as u8 + 1)
+}
+
 impl TryFrom<u8> for Symbol {
     type Error = Error;
 
@@ -238,6 +302,32 @@ impl Material {
 mod test {
     use super::*;
 
+    #[test]
+    fn element_symbol_lookup() {
+        assert_eq!(element_symbol(1), Some("H"));
+        assert_eq!(element_symbol(55), Some("Cs"));
+        assert_eq!(element_symbol(118), Some("Og"));
+        assert_eq!(element_symbol(0), None);
+        assert_eq!(element_symbol(119), None);
+
+        assert_eq!(atomic_number("U"), Some(92));
+        assert_eq!(atomic_number("Tc"), Some(43));
+        assert_eq!(atomic_number("tc"), None);
+        assert_eq!(atomic_number("Xx"), None);
+
+        assert_eq!(atomic_number_by_name("Cobalt"), Some(27));
+        assert_eq!(atomic_number_by_name("TECHNETIUM"), Some(43));
+        assert_eq!(atomic_number_by_name("cesium"), Some(55));
+        assert_eq!(atomic_number_by_name("Oganesson"), Some(118));
+        assert_eq!(atomic_number_by_name("Co"), None);
+
+        for z in 1..=118 {
+            let symbol = element_symbol(z).unwrap();
+            assert_eq!(atomic_number(symbol), Some(z));
+            assert_eq!(Symbol::try_from(z).unwrap().to_string(), symbol);
+        }
+    }
+
     struct TestData;
 
     impl AtomicMass for TestData {
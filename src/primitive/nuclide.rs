@@ -7,15 +7,21 @@ use float_pretty_print::PrettyPrintFloat;
 use num_derive::FromPrimitive;
 use num_traits::FromPrimitive;
 use serde::{Deserialize, Serialize};
-use serde_with::DeserializeFromStr;
+use serde_with::{DeserializeFromStr, SerializeDisplay};
 
-use super::notation::Symbol;
-use super::parser::{halflife, nuclide};
+use super::notation::{atomic_number, atomic_number_by_name, Symbol};
+use super::parser::halflife;
 use crate::error::Error;
+use crate::regex;
 
 pub use decay_mode::{DecayMode, DecayModeSet};
 
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, DeserializeFromStr)]
+/// Nuclide, ordered by atomic number, mass number and metastable state
+///
+/// Fission products come after all other nuclides.
+#[derive(
+    Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, SerializeDisplay, DeserializeFromStr,
+)]
 pub enum Nuclide {
     /// Nuclide with canonical id
     WithId(u32),
@@ -24,6 +30,18 @@ pub enum Nuclide {
 }
 
 impl Nuclide {
+    /// Nuclide of atomic number, mass number and metastable state (None for ground state)
+    pub fn new(z: u8, a: u32, state: Option<MetastableState>) -> Result<Self, Error> {
+        Symbol::try_from(z)?;
+        if a < u32::from(z) || a > 999 {
+            return Err(Error::InvalidNuclide(format!("Z={}, A={}", z, a)));
+        }
+
+        Ok(Self::WithId(
+            u32::from(z) * 10_000_000 + a * 10_000 + state.map_or(0, |m| m as u32),
+        ))
+    }
+
     pub fn id(&self) -> Option<u32> {
         match self {
             Self::WithId(id) => Some(*id),
@@ -47,6 +65,26 @@ impl Nuclide {
             None => None,
         }
     }
+
+    /// Whether mass number is within a plausible range of the element, Z <= A <= 3Z + 10
+    ///
+    /// The range is a loose envelope of known isotopes, e.g. H-7 and U-242.
+    pub fn is_plausible(&self) -> bool {
+        match (self.z(), self.a()) {
+            (Some(z), Some(a)) => (u32::from(z)..=3 * u32::from(z) + 10).contains(&a),
+            _ => true,
+        }
+    }
+
+    /// Parse nuclide, rejecting implausible mass numbers (see `is_plausible`)
+    pub fn from_str_strict(s: &str) -> Result<Self, Error> {
+        let nuclide: Self = s.parse()?;
+        if nuclide.is_plausible() {
+            Ok(nuclide)
+        } else {
+            Err(Error::InvalidNuclide(s.to_string()))
+        }
+    }
 }
 
 impl Display for Nuclide {
@@ -71,20 +109,83 @@ impl Display for Nuclide {
 impl FromStr for Nuclide {
     type Err = Error;
 
+    /// Parse nuclide from common notations, e.g. "Co-60", "Co60", "60Co", "Cobalt-60",
+    /// "Tc-99m", "Tc-99m2", "99mTc", "SF" (or "various") or a canonical id
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        nuclide()
-            .then_ignore(end())
-            .parse(s.trim())
-            .map_err(|_| Error::InvalidNuclide(s.to_string()))
+        let s = s.trim();
+        if s == "SF" || s == "various" {
+            return Ok(Self::FissionProducts);
+        }
+        if !s.is_empty() && s.chars().all(|c| c.is_ascii_digit()) {
+            return s
+                .parse()
+                .map(Self::WithId)
+                .map_err(|_| Error::InvalidNuclide(s.to_string()));
+        }
+
+        let (element, mass, state) =
+            if let Some(c) = regex!(r"^([A-Za-z]+)\s*-?\s*(\d+)\s*([A-Za-z*]+\d?)?$").captures(s) {
+                let z = element_z(&c[1])?;
+                let mass = c.get(2).unwrap().as_str();
+                (z, mass, c.get(3).map_or("", |m| m.as_str()))
+            } else if let Some(c) = regex!(r"^(\d+)\s*-?\s*([A-Za-z*]+)$").captures(s) {
+                // the element may be preceded by a state marker, e.g. "99mTc"
+                let element = c.get(2).unwrap().as_str();
+                let (z, state) = match element_z(element) {
+                    Ok(z) => (z, ""),
+                    Err(e) => match element.split_at(1) {
+                        (state @ ("m" | "n" | "*"), element) if !element.is_empty() => {
+                            (element_z(element).map_err(|_| e)?, state)
+                        }
+                        _ => return Err(e),
+                    },
+                };
+                (z, c.get(1).unwrap().as_str(), state)
+            } else {
+                return Err(Error::InvalidNuclide(s.to_string()));
+            };
+
+        let mass: u32 = mass
+            .parse()
+            .map_err(|_| Error::InvalidNuclide(s.to_string()))?;
+        let state = match state {
+            "" => None,
+            "m1" | "M1" => Some(MetastableState::M),
+            "m2" | "M2" => Some(MetastableState::N),
+            _ => Some(
+                state
+                    .parse::<MetastableState>()
+                    .map_err(|_| Error::InvalidState(state.to_string()))?,
+            ),
+        };
+
+        Self::new(element, mass, state).map_err(|_| Error::InvalidNuclide(s.to_string()))
     }
 }
 
+// Atomic number of an element symbol or name (both case insensitive)
+fn element_z(element: &str) -> Result<u8, Error> {
+    let z = if element.len() <= 2 {
+        let mut chars = element.chars();
+        let symbol: String = chars
+            .next()
+            .map(|c| c.to_ascii_uppercase())
+            .into_iter()
+            .chain(chars.map(|c| c.to_ascii_lowercase()))
+            .collect();
+        atomic_number(&symbol)
+    } else {
+        atomic_number_by_name(element)
+    };
+    z.ok_or_else(|| Error::InvalidSymbol(element.to_string()))
+}
+
 #[repr(u8)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, FromPrimitive)]
 pub enum MetastableState {
-    #[serde(rename = "m")]
+    #[serde(rename = "m", alias = "M", alias = "*")]
     M = 1,
-    #[serde(rename = "n")]
+    #[serde(rename = "n", alias = "N")]
     N,
 }
 
@@ -126,6 +227,25 @@ pub mod decay_mode {
             IsometricTransition,
             #[serde(rename = "SF")]
             SpontaneousFission,
+            #[serde(rename = "P")]
+            ProtonEmission,
+            #[serde(rename = "N")]
+            NeutronEmission,
+        }
+    }
+
+    impl DecayMode {
+        /// Change of (Z, A) from parent to daughter, or None for spontaneous fission
+        pub fn transition(self) -> Option<(i32, i32)> {
+            match self {
+                Self::Alpha => Some((-2, -4)),
+                Self::BetaMinus => Some((1, 0)),
+                Self::BetaPlus | Self::ElectronCapture => Some((-1, 0)),
+                Self::IsometricTransition => Some((0, 0)),
+                Self::SpontaneousFission => None,
+                Self::ProtonEmission => Some((-1, -1)),
+                Self::NeutronEmission => Some((0, -1)),
+            }
         }
     }
 
@@ -152,6 +272,8 @@ pub mod decay_mode {
                     Self::ElectronCapture => "EC",
                     Self::IsometricTransition => "IT",
                     Self::SpontaneousFission => "SF",
+                    Self::ProtonEmission => "p",
+                    Self::NeutronEmission => "n",
                 }
             )
         }
@@ -187,6 +309,10 @@ pub mod decay_mode {
                 modes.push(DecayMode::IsometricTransition.to_string());
             } else if self.0.contains(DecayMode::SpontaneousFission) {
                 modes.push(DecayMode::SpontaneousFission.to_string());
+            } else if self.0.contains(DecayMode::ProtonEmission) {
+                modes.push(DecayMode::ProtonEmission.to_string());
+            } else if self.0.contains(DecayMode::NeutronEmission) {
+                modes.push(DecayMode::NeutronEmission.to_string());
             }
 
             write!(f, "{}", modes.join("|"))
@@ -203,7 +329,7 @@ pub mod decay_mode {
             type Value = FlagSet<DecayMode>;
 
             fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-                formatter.write_str("A|B-|B+|EC|IT|SF")
+                formatter.write_str("A|B-|B+|EC|IT|SF|P|N")
             }
 
             fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
@@ -237,8 +363,13 @@ pub enum TimeUnit {
     Hour,
     #[serde(rename = "d")]
     Day,
+    #[serde(rename = "w")]
+    Week,
     #[serde(rename = "y")]
     Year,
+    /// Infinite half-life of stable nuclides
+    #[serde(rename = "stable")]
+    Stable,
 }
 
 serde_plain::derive_fromstr_from_deserialize!(TimeUnit);
@@ -255,7 +386,9 @@ impl std::fmt::Display for TimeUnit {
                 Self::Minute => "m",
                 Self::Hour => "h",
                 Self::Day => "d",
+                Self::Week => "w",
                 Self::Year => "y",
+                Self::Stable => "stable",
             }
         )
     }
@@ -270,7 +403,28 @@ impl TimeUnit {
             Self::Minute => 60.,
             Self::Hour => 3_600.,
             Self::Day => 86_400.,
-            Self::Year => 365.2422 * 86_400.,
+            Self::Week => 7. * 86_400.,
+            Self::Year => YearConvention::Tropical.days() * 86_400.,
+            Self::Stable => f64::INFINITY,
+        }
+    }
+}
+
+/// Length of year used to convert half-lives given in years
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum YearConvention {
+    /// 365.2422 days, as adopted by ICRP 107
+    #[default]
+    Tropical,
+    /// 365.25 days
+    Julian,
+}
+
+impl YearConvention {
+    pub fn days(&self) -> f64 {
+        match self {
+            Self::Tropical => 365.2422,
+            Self::Julian => 365.25,
         }
     }
 }
@@ -279,17 +433,153 @@ impl TimeUnit {
 pub struct HalfLife {
     pub value: f64,
     pub unit: TimeUnit,
+    /// Absolute uncertainty (same unit as value), e.g. `5.27(2) y`
+    pub uncertainty: Option<f64>,
+}
+
+/// Half-life of decay constant (s^-1)
+///
+/// The decay constant is non-negative, where zero is a stable nuclide. A negative or NaN
+/// decay constant has no half-life and is taken as stable as well.
+pub fn half_life_from_lambda(lambda: f64) -> HalfLife {
+    if lambda.is_nan() || lambda <= 0. {
+        HalfLife::stable()
+    } else {
+        HalfLife::from_secs(2.0_f64.ln() / lambda)
+    }
+}
+
+/// Decay constant (s^-1) of half-life
+pub fn lambda_from_half_life(hl: &HalfLife) -> f64 {
+    hl.as_lambda()
 }
 
 impl HalfLife {
-    /// Half-life in seconds
+    /// Infinite half-life of a stable nuclide
+    pub fn stable() -> Self {
+        Self {
+            value: 1.,
+            unit: TimeUnit::Stable,
+            uncertainty: None,
+        }
+    }
+
+    pub fn is_stable(&self) -> bool {
+        self.as_sec().is_infinite()
+    }
+
+    /// Half-life from seconds, in the largest unit not exceeding it
+    ///
+    /// The seconds are non-negative, where infinity is a stable nuclide. Negative or NaN
+    /// seconds are taken as stable as well, as for [`half_life_from_lambda`].
+    pub fn from_secs(secs: f64) -> Self {
+        if secs.is_infinite() || secs.is_nan() || secs < 0. {
+            return Self::stable();
+        }
+
+        let unit = [
+            TimeUnit::Year,
+            TimeUnit::Day,
+            TimeUnit::Hour,
+            TimeUnit::Minute,
+            TimeUnit::Second,
+            TimeUnit::MilliSecond,
+        ]
+        .into_iter()
+        .find(|unit| secs >= unit.as_sec())
+        .unwrap_or(TimeUnit::MicroSecond);
+
+        Self {
+            value: secs / unit.as_sec(),
+            unit,
+            uncertainty: None,
+        }
+    }
+
+    /// Half-life in seconds (central value)
     pub fn as_sec(&self) -> f64 {
-        self.value * self.unit.as_sec()
+        match self.unit {
+            TimeUnit::Stable => f64::INFINITY,
+            unit => self.value * unit.as_sec(),
+        }
+    }
+
+    /// Half-life (central value) expressed in unit
+    pub fn as_unit(&self, unit: TimeUnit) -> f64 {
+        if self.is_stable() {
+            f64::INFINITY
+        } else {
+            self.as_sec() / unit.as_sec()
+        }
     }
 
-    /// Decay constant (s^-1)
+    /// Same half-life in the most readable unit, as chosen by [`HalfLife::from_secs`]
+    ///
+    /// Weeks are never chosen, e.g. 90 days stays in days and 400 days becomes 1.095 y.
+    pub fn normalized(&self) -> HalfLife {
+        let mut half_life = Self::from_secs(self.as_sec());
+        half_life.uncertainty = self
+            .uncertainty_as_sec()
+            .map(|u| u / half_life.unit.as_sec());
+        half_life
+    }
+
+    /// Effective half-life of physical and biological removal, `1/T = 1/T_p + 1/T_b`
+    ///
+    /// If either half-life is stable, the other is returned unchanged. Otherwise the
+    /// result is in the unit chosen by [`HalfLife::from_secs`], without uncertainty.
+    pub fn effective(&self, biological: HalfLife) -> HalfLife {
+        if self.is_stable() {
+            biological
+        } else if biological.is_stable() {
+            *self
+        } else {
+            Self::from_secs(1. / (1. / self.as_sec() + 1. / biological.as_sec()))
+        }
+    }
+
+    /// Uncertainty of half-life in seconds
+    pub fn uncertainty_as_sec(&self) -> Option<f64> {
+        self.uncertainty.map(|u| u * self.unit.as_sec())
+    }
+
+    /// Decay constant (s^-1), which is zero for a stable nuclide
     pub fn as_lambda(&self) -> f64 {
-        2.0_f64.ln() / self.as_sec()
+        if self.is_stable() {
+            0.
+        } else {
+            2.0_f64.ln() / self.as_sec()
+        }
+    }
+
+    /// Decay constant (s^-1) and its absolute uncertainty
+    ///
+    /// The relative uncertainty of the decay constant equals the one of half-life,
+    /// which is zero if the half-life has no uncertainty.
+    pub fn as_lambda_with_uncertainty(&self) -> (f64, f64) {
+        let lambda = self.as_lambda();
+        let relative = self.uncertainty.map_or(0., |u| u / self.value);
+
+        (lambda, lambda * relative)
+    }
+
+    /// Deterministic representation, e.g. `30.08 y`
+    ///
+    /// The value is rounded to 6 significant digits without trailing zeros, and written
+    /// in scientific notation (`1.5e-7 s`) if its exponent is below -5 or above 5.
+    pub fn to_canonical_string(&self) -> String {
+        if self.is_stable() {
+            return self.to_string();
+        }
+        format!("{} {}", canonical_float(self.value), self.unit)
+    }
+
+    /// Representation in scientific notation regardless of magnitude, e.g. `5.27e0 y`
+    pub fn to_scientific(&self) -> String {
+        if self.is_stable() {
+            return self.to_string();
+        }
+        format!("{:e} {}", self.value, self.unit)
     }
 }
 
@@ -297,12 +587,18 @@ impl FromStr for HalfLife {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim().eq_ignore_ascii_case("stable") {
+            return Ok(Self::stable());
+        }
         halflife().then_ignore(end()).parse(s).map_err(|e| e.into())
     }
 }
 
 impl std::fmt::Display for HalfLife {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_stable() {
+            return write!(f, "{}", TimeUnit::Stable);
+        }
         let number_str = PrettyPrintFloat(self.value).to_string();
         match number_str.strip_suffix(".0") {
             Some(number_str) => write!(f, "{} {}", number_str, self.unit),
@@ -311,6 +607,43 @@ impl std::fmt::Display for HalfLife {
     }
 }
 
+fn canonical_float(value: f64) -> String {
+    if value == 0. || !value.is_finite() {
+        return value.to_string();
+    }
+
+    let sci = format!("{:.5e}", value);
+    let (mantissa, exp) = sci.split_once('e').unwrap();
+    let exp: i32 = exp.parse().unwrap();
+
+    let trim = |s: String| s.trim_end_matches('0').trim_end_matches('.').to_string();
+    if (-5..6).contains(&exp) {
+        trim(format!("{:.*}", (5 - exp).max(0) as usize, value))
+    } else {
+        format!("{}e{}", trim(mantissa.to_string()), exp)
+    }
+}
+
+/// Half-life as an exact string, which parses back to the same value, e.g. `30.08(9) y`
+impl Serialize for HalfLife {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.is_stable() {
+            return serializer.collect_str(&TimeUnit::Stable);
+        }
+
+        let decimals = |x: f64| x.to_string().split_once('.').map_or(0, |(_, d)| d.len());
+        let s = match self.uncertainty {
+            Some(u) => {
+                let n = decimals(self.value).max(decimals(u));
+                let digits = (u * 10f64.powi(n as i32)).round();
+                format!("{:.*}({}) {}", n, self.value, digits, self.unit)
+            }
+            None => format!("{} {}", self.value, self.unit),
+        };
+        serializer.serialize_str(&s)
+    }
+}
+
 impl PartialEq for HalfLife {
     fn eq(&self, other: &Self) -> bool {
         self.as_sec() == other.as_sec()
@@ -319,6 +652,8 @@ impl PartialEq for HalfLife {
 
 #[cfg(test)]
 mod test {
+    use std::collections::HashMap;
+
     use super::*;
 
     #[test]
@@ -329,8 +664,223 @@ mod test {
         let tc99m: Nuclide = "Tc-99m".parse().unwrap();
         assert_eq!(tc99m.id().unwrap(), 430990001);
 
+        let tc99: Nuclide = "Tc99".parse().unwrap();
+        assert_eq!(tc99.id().unwrap(), 430990000);
+        let tc99m_from_id: Nuclide = "430990001".parse().unwrap();
+        assert_eq!(tc99m_from_id, tc99m);
+
         let cc99: Result<Nuclide, Error> = "Cc-99".parse();
         assert!(cc99.is_err());
+        assert!("Tc-99o".parse::<Nuclide>().is_err());
+    }
+
+    #[test]
+    fn nuclide_from_string_strict() {
+        assert!("Co-999".parse::<Nuclide>().is_ok());
+        assert!(matches!(
+            Nuclide::from_str_strict("Co-999"),
+            Err(Error::InvalidNuclide(_))
+        ));
+        assert!(Nuclide::from_str_strict("Fe-20").is_err());
+
+        assert!(Nuclide::from_str_strict("Co-60").is_ok());
+        assert!(Nuclide::from_str_strict("H-3").is_ok());
+        assert!(Nuclide::from_str_strict("U-238").is_ok());
+        assert!(Nuclide::from_str_strict("Tc-99m").is_ok());
+        assert!(Nuclide::from_str_strict("SF").is_ok());
+    }
+
+    #[test]
+    fn nuclide_state_markers() {
+        let tc99m: Nuclide = "Tc-99m".parse().unwrap();
+        for s in ["Tc-99M", "Tc-99*", "Tc99m"] {
+            assert_eq!(s.parse::<Nuclide>().unwrap(), tc99m);
+        }
+        assert_eq!(tc99m.state(), Some(MetastableState::M));
+        assert_eq!("Tc-99*".parse::<Nuclide>().unwrap().to_string(), "Tc-99m");
+
+        let n: Nuclide = "Ir-192N".parse().unwrap();
+        assert_eq!(n.state(), Some(MetastableState::N));
+
+        assert!(matches!(
+            "Tc-99x".parse::<Nuclide>(),
+            Err(Error::InvalidState(s)) if s == "x"
+        ));
+        assert!(matches!(
+            "Tc-99**".parse::<Nuclide>(),
+            Err(Error::InvalidState(_))
+        ));
+        assert!(matches!(
+            "Tc-99-m".parse::<Nuclide>(),
+            Err(Error::InvalidNuclide(_))
+        ));
+    }
+
+    #[test]
+    fn serde_round_trip() {
+        let half_lives: HashMap<Nuclide, HalfLife> = [
+            ("Co-60", "5.2713 y"),
+            ("Tc-99m", "6.015 h"),
+            ("Tc-99n", "0.18 us"),
+            ("Cs-137", "30.08(9) y"),
+            ("U-238", "4.468e9 y"),
+            ("Ba-137", "stable"),
+            ("various", "1.5e-7 s"),
+        ]
+        .into_iter()
+        .map(|(n, t)| (n.parse().unwrap(), t.parse().unwrap()))
+        .collect();
+
+        let s = serde_json::to_string(&half_lives).unwrap();
+        assert!(s.contains(r#""Tc-99m":"6.015 h""#));
+        assert!(s.contains(r#""Ba-137":"stable""#));
+        assert!(s.contains(r#""Cs-137":"30.08(9) y""#));
+
+        let parsed: HashMap<Nuclide, HalfLife> = serde_json::from_str(&s).unwrap();
+        assert_eq!(parsed, half_lives);
+        assert!(parsed[&"Ba-137".parse().unwrap()].is_stable());
+
+        let cs137 = parsed[&"Cs-137".parse().unwrap()];
+        assert_eq!(cs137.unit, TimeUnit::Year);
+        assert!((cs137.uncertainty.unwrap() - 0.09).abs() < 1e-12);
+
+        // uncertainty finer than the value is written with more decimals
+        let t = HalfLife {
+            value: 5.,
+            unit: TimeUnit::Day,
+            uncertainty: Some(0.3),
+        };
+        assert_eq!(serde_plain::to_string(&t).unwrap(), "5.0(3) d");
+    }
+
+    #[test]
+    fn nuclide_from_numbers() {
+        let tc99m = Nuclide::new(43, 99, Some(MetastableState::M)).unwrap();
+        assert_eq!(tc99m.to_string(), "Tc-99m");
+        assert_eq!(tc99m, "Tc-99m".parse().unwrap());
+        assert_eq!(
+            (tc99m.z(), tc99m.a(), tc99m.state()),
+            (Some(43), Some(99), Some(MetastableState::M))
+        );
+
+        let co60 = Nuclide::new(27, 60, None).unwrap();
+        assert_eq!(co60.to_string(), "Co-60");
+        assert_eq!(co60.state(), None);
+
+        assert!(matches!(
+            Nuclide::new(0, 10, None),
+            Err(Error::InvalidAtomicNumber(0))
+        ));
+        assert!(matches!(
+            Nuclide::new(119, 300, None),
+            Err(Error::InvalidAtomicNumber(119))
+        ));
+        assert!(matches!(
+            Nuclide::new(27, 26, None),
+            Err(Error::InvalidNuclide(_))
+        ));
+        assert!(Nuclide::new(27, 1000, None).is_err());
+    }
+
+    #[test]
+    fn nuclide_notations() {
+        let co60 = Nuclide::WithId(270600000);
+        for s in [
+            "Co-60",
+            "Co60",
+            "60Co",
+            "60-Co",
+            "Cobalt-60",
+            "cobalt60",
+            "COBALT-60",
+            "co-60",
+            "CO60",
+            " Co-60 ",
+            "Co - 60",
+            "Co 60",
+            "60 Co",
+            "\tCo-60\n",
+            "270600000",
+        ] {
+            assert_eq!(s.parse::<Nuclide>().unwrap(), co60, "{:?}", s);
+            assert_eq!(s.parse::<Nuclide>().unwrap().to_string(), "Co-60");
+        }
+
+        let tc99m = Nuclide::WithId(430990001);
+        for s in [
+            "Tc-99m",
+            "Tc99m",
+            "tc-99M",
+            "Tc-99m1",
+            "99mTc",
+            "Technetium-99m",
+            "Tc 99 m",
+        ] {
+            assert_eq!(s.parse::<Nuclide>().unwrap(), tc99m, "{:?}", s);
+            assert_eq!(s.parse::<Nuclide>().unwrap().to_string(), "Tc-99m");
+        }
+
+        let tc99n = Nuclide::WithId(430990002);
+        for s in ["Tc-99m2", "Tc99n", "Tc-99N", "99nTc"] {
+            assert_eq!(s.parse::<Nuclide>().unwrap(), tc99n, "{:?}", s);
+        }
+        assert_eq!(tc99n.to_string(), "Tc-99n");
+
+        assert_eq!(
+            "Cesium-137".parse::<Nuclide>().unwrap().to_string(),
+            "Cs-137"
+        );
+        assert_eq!(
+            "Caesium-137".parse::<Nuclide>().unwrap().to_string(),
+            "Cs-137"
+        );
+        assert_eq!("3H".parse::<Nuclide>().unwrap().to_string(), "H-3");
+        assert_eq!("u238".parse::<Nuclide>().unwrap().to_string(), "U-238");
+        assert_eq!("242mAm".parse::<Nuclide>().unwrap().to_string(), "Am-242m");
+        assert_eq!(" SF ".parse::<Nuclide>().unwrap(), Nuclide::FissionProducts);
+
+        for s in ["Cc-99", "Xx60", "60Xx", "Cobalto-60", "99mXx"] {
+            assert!(
+                matches!(s.parse::<Nuclide>(), Err(Error::InvalidSymbol(_))),
+                "{:?}",
+                s
+            );
+        }
+        for s in [
+            "Co-0", "U-10", "Co-1000", "Co-", "", "-", "Co-60-", "Co--60",
+        ] {
+            assert!(
+                matches!(s.parse::<Nuclide>(), Err(Error::InvalidNuclide(_))),
+                "{:?}",
+                s
+            );
+        }
+        assert!(matches!(
+            "Tc-99m3".parse::<Nuclide>(),
+            Err(Error::InvalidState(s)) if s == "m3"
+        ));
+    }
+
+    #[test]
+    fn nuclide_ordering() {
+        let mut nuclides: Vec<Nuclide> = [
+            "Tc-99m", "U-238", "SF", "H-3", "Tc-99", "U-235", "Co-60", "Tc-99n",
+        ]
+        .iter()
+        .map(|s| s.parse().unwrap())
+        .collect();
+        nuclides.sort();
+
+        let names: Vec<String> = nuclides.iter().map(|n| n.to_string()).collect();
+        assert_eq!(
+            names,
+            ["H-3", "Co-60", "Tc-99", "Tc-99m", "Tc-99n", "U-235", "U-238", "various"]
+        );
+
+        let tc99: Nuclide = "Tc-99".parse().unwrap();
+        assert!(tc99 < "Tc-99m".parse().unwrap());
+        assert!("Co-60".parse::<Nuclide>().unwrap() < "Ni-56".parse().unwrap());
+        assert_eq!(nuclides.binary_search(&tc99), Ok(2));
     }
 
     #[test]
@@ -353,15 +903,167 @@ mod test {
         );
     }
 
+    #[test]
+    fn decay_mode_codes() {
+        for (code, mode) in [
+            ("A", DecayMode::Alpha),
+            ("B-", DecayMode::BetaMinus),
+            ("B+", DecayMode::BetaPlus),
+            ("EC", DecayMode::ElectronCapture),
+            ("IT", DecayMode::IsometricTransition),
+            ("SF", DecayMode::SpontaneousFission),
+            ("P", DecayMode::ProtonEmission),
+            ("n", DecayMode::NeutronEmission),
+        ] {
+            assert_eq!(code.parse::<DecayMode>().unwrap(), mode);
+        }
+        assert_eq!(DecayMode::ProtonEmission.to_string(), "p");
+        assert!("Q".parse::<DecayMode>().is_err());
+
+        // modes of NDX records, e.g. Ac-226, Bi-212 and Cf-252
+        let modes = |s: &str| decay_mode::deserialize(serde_plain::Deserializer::new(s)).unwrap();
+        assert_eq!(
+            modes("B-ECA"),
+            DecayMode::BetaMinus | DecayMode::ElectronCapture | DecayMode::Alpha
+        );
+        assert_eq!(modes("B-A"), DecayMode::BetaMinus | DecayMode::Alpha);
+        assert_eq!(
+            modes("ECB+"),
+            DecayMode::ElectronCapture | DecayMode::BetaPlus
+        );
+        assert_eq!(
+            modes("ITB-"),
+            DecayMode::IsometricTransition | DecayMode::BetaMinus
+        );
+        assert_eq!(
+            modes("ASF"),
+            DecayMode::Alpha | DecayMode::SpontaneousFission
+        );
+        assert_eq!(
+            modes("B-N"),
+            DecayMode::BetaMinus | DecayMode::NeutronEmission
+        );
+    }
+
+    #[test]
+    fn decay_mode_transition() {
+        let daughter = |parent: &str, mode: DecayMode| {
+            let parent: Nuclide = parent.parse().unwrap();
+            let (dz, da) = mode.transition().unwrap();
+            let z = i32::from(parent.z().unwrap()) + dz;
+            let a = parent.a().unwrap() as i32 + da;
+            Nuclide::WithId(z as u32 * 10_000_000 + a as u32 * 10_000)
+        };
+
+        assert_eq!(daughter("U-238", DecayMode::Alpha).to_string(), "Th-234");
+        assert_eq!(
+            daughter("Cs-137", DecayMode::BetaMinus).to_string(),
+            "Ba-137"
+        );
+        assert_eq!(daughter("Na-22", DecayMode::BetaPlus).to_string(), "Ne-22");
+        assert_eq!(
+            daughter("Tc-99m", DecayMode::IsometricTransition).to_string(),
+            "Tc-99"
+        );
+        assert_eq!(DecayMode::SpontaneousFission.transition(), None);
+    }
+
     fn isclose(a: f64, b: f64) -> bool {
         (a - b).abs() <= f64::EPSILON
     }
 
+    #[test]
+    fn half_life_in_unit() {
+        let t: HalfLife = "2 w".parse().unwrap();
+        assert_eq!(t.unit, TimeUnit::Week);
+        assert_eq!(t.to_string(), "2 w");
+        assert_eq!(t.as_sec(), 14. * 86_400.);
+        assert_eq!(t.as_unit(TimeUnit::Day), 14.);
+
+        let t: HalfLife = "36 h".parse().unwrap();
+        assert_eq!(t.as_unit(TimeUnit::Day), 1.5);
+        let t: HalfLife = "1.5 d".parse().unwrap();
+        assert_eq!(t.as_unit(TimeUnit::Hour), 36.);
+
+        let t: HalfLife = "90 d".parse().unwrap();
+        assert_eq!(t.normalized().unit, TimeUnit::Day);
+        assert_eq!(t.normalized().value, 90.);
+
+        let t: HalfLife = "400(4) d".parse().unwrap();
+        let n = t.normalized();
+        assert_eq!(n.unit, TimeUnit::Year);
+        assert!((n.value - 400. / 365.2422).abs() < 1e-12);
+        assert!((n.uncertainty.unwrap() - 4. / 365.2422).abs() < 1e-12);
+        assert_eq!(n, t);
+
+        assert_eq!(HalfLife::stable().as_unit(TimeUnit::Year), f64::INFINITY);
+    }
+
+    #[test]
+    fn effective_half_life() {
+        let physical: HalfLife = "8 d".parse().unwrap();
+        let biological: HalfLife = "80 d".parse().unwrap();
+
+        let t = physical.effective(biological);
+        assert_eq!(t.unit, TimeUnit::Day);
+        assert!((t.value - 80. / 11.).abs() < 1e-12);
+        assert!((t.value - 7.27).abs() < 0.01);
+        assert_eq!(biological.effective(physical), t);
+
+        let t = HalfLife::stable().effective(biological);
+        assert_eq!(t.unit, TimeUnit::Day);
+        assert_eq!(t.value, 80.);
+        assert_eq!(physical.effective(HalfLife::stable()).value, 8.);
+        assert!(HalfLife::stable().effective(HalfLife::stable()).is_stable());
+    }
+
+    #[test]
+    fn half_life_from_secs() {
+        let t = HalfLife::from_secs(86400.);
+        assert_eq!(t.unit, TimeUnit::Day);
+        assert_eq!(t.to_string(), "1 d");
+        assert_eq!(t.as_sec(), 86400.);
+        assert!(t.uncertainty.is_none());
+
+        assert_eq!(HalfLife::from_secs(90.).to_string(), "1.5 m");
+        assert_eq!(HalfLife::from_secs(1e-3).unit, TimeUnit::MilliSecond);
+    }
+
+    #[test]
+    fn half_life_with_uncertainty() {
+        let t: HalfLife = "30.08(9) y".parse().unwrap();
+        assert_eq!(t.value, 30.08);
+        assert_eq!(t.unit, TimeUnit::Year);
+        assert!((t.uncertainty.unwrap() - 0.09).abs() < 1e-12);
+        assert_eq!(t, "30.08 y".parse().unwrap());
+
+        assert!("30.08 y".parse::<HalfLife>().unwrap().uncertainty.is_none());
+    }
+
+    #[test]
+    fn stable_half_life() {
+        let stable: HalfLife = "stable".parse().unwrap();
+        assert!(stable.is_stable());
+        assert_eq!(stable.as_sec(), f64::INFINITY);
+        assert_eq!(stable.as_lambda(), 0.);
+        assert_eq!(stable.as_lambda_with_uncertainty(), (0., 0.));
+
+        assert_eq!(stable.to_string(), "stable");
+        assert_eq!(stable.to_string().parse::<HalfLife>().unwrap(), stable);
+        assert_eq!(" Stable".parse::<HalfLife>().unwrap(), stable);
+        assert_eq!(stable.to_canonical_string(), "stable");
+        assert_eq!(HalfLife::from_secs(f64::INFINITY), stable);
+        assert_eq!(half_life_from_lambda(0.).to_string(), "stable");
+
+        assert!(!"30.08 y".parse::<HalfLife>().unwrap().is_stable());
+    }
+
     #[test]
     fn halflife_from_string() {
         let t1: HalfLife = "1 us".parse().unwrap();
         assert!(isclose(t1.value, 1.));
         assert_eq!(t1.unit, TimeUnit::MicroSecond);
+        assert_eq!(t1.to_string().parse::<HalfLife>().unwrap(), t1);
 
         let t2: HalfLife = "2h".parse().unwrap();
         assert!(isclose(t2.value, 2.));
@@ -376,6 +1078,76 @@ mod test {
         assert_eq!(t4.unit, TimeUnit::Second);
     }
 
+    #[test]
+    fn halflife_canonical_string() {
+        let canonical = |s: &str| s.parse::<HalfLife>().unwrap().to_canonical_string();
+        assert_eq!(canonical("30.08y"), "30.08 y");
+        assert_eq!(canonical("10y"), "10 y");
+        assert_eq!(canonical("2.0h"), "2 h");
+        assert_eq!(canonical("1.2345678d"), "1.23457 d");
+        assert_eq!(canonical("0.000123s"), "0.000123 s");
+        assert_eq!(canonical("4.468e9y"), "4.468e9 y");
+        assert_eq!(canonical("1.5e-7s"), "1.5e-7 s");
+        assert_eq!(canonical("999999.9y"), "1e6 y");
+        assert_eq!(canonical("123456.7s"), "123457 s");
+    }
+
+    #[test]
+    fn halflife_scientific_string() {
+        let scientific = |s: &str| s.parse::<HalfLife>().unwrap().to_scientific();
+        assert_eq!(scientific("5.27y"), "5.27e0 y");
+        assert_eq!(scientific("2h"), "2e0 h");
+        assert_eq!(scientific("4.468e9y"), "4.468e9 y");
+        assert_eq!(scientific("1600y"), "1.6e3 y");
+        assert_eq!(scientific("0.000123s"), "1.23e-4 s");
+        assert_eq!(scientific("1.5e-7s"), "1.5e-7 s");
+    }
+
+    #[test]
+    fn halflife_lambda_round_trip() {
+        let t: HalfLife = "30.08 y".parse().unwrap();
+        let t2 = half_life_from_lambda(lambda_from_half_life(&t));
+        assert_eq!(t2.unit, TimeUnit::Year);
+        assert!((t2.value / 30.08 - 1.).abs() < 1e-12);
+
+        let t = half_life_from_lambda(2.0_f64.ln() / 5400.);
+        assert_eq!(t.unit, TimeUnit::Hour);
+        assert!((t.value / 1.5 - 1.).abs() < 1e-12);
+        assert!((lambda_from_half_life(&t) * 5400. / 2.0_f64.ln() - 1.).abs() < 1e-12);
+
+        let t = HalfLife::from_secs(2.5e-4);
+        assert_eq!(t.unit, TimeUnit::MicroSecond);
+        assert!((t.value / 250. - 1.).abs() < 1e-12);
+        assert_eq!(HalfLife::from_secs(86_400.).unit, TimeUnit::Day);
+    }
+
+    #[test]
+    fn halflife_of_invalid_lambda() {
+        assert!(half_life_from_lambda(-1e-9).is_stable());
+        assert!(half_life_from_lambda(f64::NAN).is_stable());
+        assert!(half_life_from_lambda(f64::NEG_INFINITY).is_stable());
+
+        assert!(HalfLife::from_secs(-1.).is_stable());
+        assert!(HalfLife::from_secs(f64::NAN).is_stable());
+        assert_eq!(HalfLife::from_secs(0.).as_sec(), 0.);
+    }
+
+    #[test]
+    fn halflife_with_uncertainty_from_string() {
+        let t1: HalfLife = "5.27(2) y".parse().unwrap();
+        assert!(isclose(t1.value, 5.27));
+        assert_eq!(t1.unit, TimeUnit::Year);
+        assert!((t1.uncertainty.unwrap() - 0.02).abs() < 1e-12);
+        assert!(isclose(t1.as_sec(), 5.27 * 365.2422 * 86400.));
+
+        let t2: HalfLife = "2.6(12)h".parse().unwrap();
+        assert!(isclose(t2.value, 2.6));
+        assert!((t2.uncertainty_as_sec().unwrap() - 1.2 * 3600.).abs() < 1e-9);
+
+        let t3: HalfLife = "10y".parse().unwrap();
+        assert_eq!(t3.uncertainty, None);
+    }
+
     #[test]
     fn halflife_to_string() {
         let t1: HalfLife = "1us".parse().unwrap();
@@ -1,9 +1,9 @@
-use chumsky::prelude::{filter, just, recursive, text, Parser, Simple};
+use chumsky::prelude::{filter, just, recursive, Parser, Simple};
 use chumsky::text::TextParser;
 use flagset::FlagSet;
 
 use super::notation::{Compound, Symbol};
-use super::nuclide::{DecayMode, HalfLife, MetastableState, Nuclide, TimeUnit};
+use super::nuclide::{DecayMode, HalfLife, TimeUnit};
 
 pub fn symbol() -> impl Parser<char, Symbol, Error = Simple<char>> {
     filter(|c: &char| c.is_ascii_uppercase())
@@ -16,47 +16,6 @@ pub fn symbol() -> impl Parser<char, Symbol, Error = Simple<char>> {
         })
 }
 
-pub fn nuclide() -> impl Parser<char, Nuclide, Error = Simple<char>> {
-    let sf = text::keyword("SF").map(|_| Nuclide::FissionProducts);
-
-    let symbol = symbol().map(|s| s as u32 * 10_000_000);
-
-    let mass = text::int(10)
-        .try_map(|s: String, span| {
-            s.parse::<u32>()
-                .map_err(|e| Simple::custom(span, format!("{}", e)))
-        })
-        .map(|m| m * 10_000);
-
-    let meta = filter(|c: &char| c.is_ascii_alphabetic())
-        .repeated()
-        .at_most(1)
-        .try_map(|meta, span| {
-            let meta = meta.into_iter().collect::<String>();
-            if !meta.is_empty() {
-                meta.parse::<MetastableState>()
-                    .map_err(|e| Simple::custom(span, format!("{}", e)))
-                    .map(|m| m as u32)
-            } else {
-                Ok(0)
-            }
-        });
-
-    let with_name = sf.or(symbol
-        .chain(just('-').repeated().map(|_| 0u32))
-        .chain(mass)
-        .chain(meta)
-        .map(|v| Nuclide::WithId(v.into_iter().sum())));
-
-    let with_id = text::int(10).try_map(|s: String, span| {
-        s.parse::<u32>()
-            .map_err(|e| Simple::custom(span, format!("{}", e)))
-            .map(Nuclide::WithId)
-    });
-
-    with_name.or(with_id)
-}
-
 pub fn decaymode() -> impl Parser<char, DecayMode, Error = Simple<char>> {
     let a = just("A").or(just("⍺")).map(|_| DecayMode::Alpha).padded();
     let bm = just("B-")
@@ -70,8 +29,16 @@ pub fn decaymode() -> impl Parser<char, DecayMode, Error = Simple<char>> {
     let ec = just("EC").map(|_| DecayMode::ElectronCapture).padded();
     let it = just("IT").map(|_| DecayMode::IsometricTransition).padded();
     let sf = just("SF").map(|_| DecayMode::SpontaneousFission).padded();
+    let p = just("P")
+        .or(just("p"))
+        .map(|_| DecayMode::ProtonEmission)
+        .padded();
+    let n = just("N")
+        .or(just("n"))
+        .map(|_| DecayMode::NeutronEmission)
+        .padded();
 
-    a.or(bm.or(bp.or(ec.or(it.or(sf)))))
+    a.or(bm.or(bp.or(ec.or(it.or(sf.or(p.or(n)))))))
 }
 
 pub fn decaymodeflags() -> impl Parser<char, FlagSet<DecayMode>, Error = Simple<char>> {
@@ -107,6 +74,14 @@ pub fn compound() -> impl Parser<char, Compound, Error = Simple<char>> {
 }
 
 pub fn float() -> impl Parser<char, f64, Error = Simple<char>> {
+    float_literal().try_map(|s, span| {
+        s.parse::<f64>()
+            .map_err(|e| Simple::custom(span, format!("{}", e)))
+    })
+}
+
+// Textual form of a float number, kept for notations depending on its digits
+fn float_literal() -> impl Parser<char, String, Error = Simple<char>> {
     let ch = |c: char| just(c).map(|v| vec![v]);
 
     let sign = ch('+').or(ch('-')).or_not().map(|v| v.unwrap_or_default());
@@ -128,29 +103,64 @@ pub fn float() -> impl Parser<char, f64, Error = Simple<char>> {
             .map(|v: Option<Vec<char>>| v.unwrap_or_default()),
     );
 
-    scientific.try_map(|chs: Vec<char>, span| {
-        chs.into_iter()
-            .collect::<String>()
-            .parse::<f64>()
-            .map_err(|e| Simple::custom(span, format!("{}", e)))
-    })
+    scientific.map(|chs: Vec<char>| chs.into_iter().collect())
+}
+
+// Uncertainty in parenthesized notation, e.g. `5.27(2)` stands for `5.27 ± 0.02`
+fn uncertain_float() -> impl Parser<char, (f64, Option<f64>), Error = Simple<char>> {
+    let digits = filter(|c: &char| c.is_ascii_digit())
+        .repeated()
+        .at_least(1)
+        .collect::<String>()
+        .delimited_by(just('('), just(')'));
+
+    float_literal()
+        .then(digits.or_not())
+        .try_map(|(s, digits), span| {
+            let value = s
+                .parse::<f64>()
+                .map_err(|e| Simple::custom(span.clone(), format!("{}", e)))?;
+
+            let uncertainty = match digits {
+                Some(digits) => {
+                    let (mantissa, exponent) = match s.split_once(['e', 'E']) {
+                        Some((mantissa, exponent)) => (mantissa, exponent.parse().unwrap_or(0)),
+                        None => (s.as_str(), 0),
+                    };
+                    let decimals = mantissa.split_once('.').map_or(0, |(_, d)| d.len() as i32);
+                    let digits = digits
+                        .parse::<f64>()
+                        .map_err(|e| Simple::custom(span, format!("{}", e)))?;
+
+                    Some(digits * 10f64.powi(exponent - decimals))
+                }
+                None => None,
+            };
+
+            Ok((value, uncertainty))
+        })
 }
 
 pub fn halflife() -> impl Parser<char, HalfLife, Error = Simple<char>> {
-    let us = just("us").map(|_| TimeUnit::MicroSecond);
-    let ms = just("ms").map(|_| TimeUnit::MicroSecond);
+    let us = just("us").or(just("μs")).map(|_| TimeUnit::MicroSecond);
+    let ms = just("ms").map(|_| TimeUnit::MilliSecond);
     let s = just("s").map(|_| TimeUnit::Second);
     let m = just("m").map(|_| TimeUnit::Minute);
     let h = just("h").map(|_| TimeUnit::Hour);
     let d = just("d").map(|_| TimeUnit::Day);
+    let w = just("w").map(|_| TimeUnit::Week);
     let y = just("y").map(|_| TimeUnit::Year);
 
-    let unit = us.or(ms.or(s.or(m.or(h.or(d.or(y))))));
+    let unit = us.or(ms.or(s.or(m.or(h.or(d.or(w.or(y)))))));
 
-    float()
+    uncertain_float()
         .padded()
         .then(unit)
-        .map(|(value, unit)| HalfLife { value, unit })
+        .map(|((value, uncertainty), unit)| HalfLife {
+            value,
+            unit,
+            uncertainty,
+        })
 }
 
 pub fn gi_absorption_factor() -> impl Parser<char, (f64, String), Error = Simple<char>> {
@@ -177,24 +187,6 @@ mod test {
         assert!(cc.is_err());
     }
 
-    #[test]
-    fn parse_nuclide() {
-        let tc99 = nuclide().parse("Tc99").unwrap();
-        assert_eq!(tc99, Nuclide::WithId(43_099_0000));
-
-        let tc99m = nuclide().parse("Tc-99m").unwrap();
-        assert_eq!(tc99m, Nuclide::WithId(43_099_0001));
-
-        let tc99m_from_id = nuclide().parse("430990001").unwrap();
-        assert_eq!(tc99m_from_id, Nuclide::WithId(43_099_0001));
-
-        let tc99o = nuclide().parse("Tc-99o");
-        assert!(tc99o.is_err());
-
-        let cc99 = nuclide().parse("Cc-99");
-        assert!(cc99.is_err());
-    }
-
     #[test]
     fn parse_decaymodeflags() {
         // formatter.write_str("A|B-|B+|EC|IT|SF")
@@ -263,7 +255,8 @@ mod test {
             h1,
             HalfLife {
                 value: 0.0123,
-                unit: TimeUnit::Second
+                unit: TimeUnit::Second,
+                uncertainty: None,
             }
         );
 
@@ -272,9 +265,30 @@ mod test {
             h2,
             HalfLife {
                 value: 321.,
-                unit: TimeUnit::Hour
+                unit: TimeUnit::Hour,
+                uncertainty: None,
             }
-        )
+        );
+
+        let h3 = halflife().parse("5.27(2) y").unwrap();
+        assert_eq!(h3.value, 5.27);
+        assert_eq!(h3.unit, TimeUnit::Year);
+        assert!((h3.uncertainty.unwrap() - 0.02).abs() < 1e-12);
+
+        let h4 = halflife().parse("1.23e-2(15)s").unwrap();
+        assert_eq!(h4.value, 0.0123);
+        assert!((h4.uncertainty.unwrap() - 0.0015).abs() < 1e-12);
+
+        assert_eq!(halflife().parse("2 w").unwrap().unit, TimeUnit::Week);
+        assert_eq!(
+            halflife().parse("2 ms").unwrap().unit,
+            TimeUnit::MilliSecond
+        );
+        assert_eq!(halflife().parse("2 m").unwrap().unit, TimeUnit::Minute);
+        assert_eq!(
+            halflife().parse("2 us").unwrap().unit,
+            TimeUnit::MicroSecond
+        );
     }
 
     #[test]
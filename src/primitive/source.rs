@@ -0,0 +1,69 @@
+use chrono::{DateTime, Utc};
+
+use super::attr::DecayConstant;
+use super::nuclide::Nuclide;
+use crate::error::Error;
+
+/// Radioactive source of known activity at a reference time
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Source {
+    pub nuclide: Nuclide,
+    /// Activity (Bq) at reference time
+    pub activity: f64,
+    pub reference: DateTime<Utc>,
+}
+
+impl Source {
+    pub fn new(nuclide: Nuclide, activity: f64, reference: DateTime<Utc>) -> Self {
+        Self {
+            nuclide,
+            activity,
+            reference,
+        }
+    }
+
+    /// Activity (Bq) at given time, which may also be before the reference time
+    pub fn activity_at<D: DecayConstant>(
+        &self,
+        dataset: &D,
+        when: DateTime<Utc>,
+    ) -> Result<f64, Error> {
+        let elapsed = (when - self.reference).num_milliseconds() as f64 / 1e3;
+        Ok(self.activity * (-dataset.lambda(self.nuclide)? * elapsed).exp())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::primitive::attr::NuclideHalfLife;
+    use crate::primitive::HalfLife;
+
+    struct TestData;
+
+    impl NuclideHalfLife for TestData {
+        fn half_life(&self, nuclide: Nuclide) -> Result<HalfLife, Error> {
+            if nuclide == "P-32".parse().unwrap() {
+                "14.268d".parse()
+            } else {
+                Err(Error::InvalidNuclide(nuclide.to_string()))
+            }
+        }
+    }
+
+    #[test]
+    fn activity_at() {
+        let reference: DateTime<Utc> = "2020-01-01T00:00:00Z".parse().unwrap();
+        let source = Source::new("P-32".parse().unwrap(), 1e6, reference);
+
+        // two half-lives later
+        let when = reference + chrono::Duration::milliseconds((2. * 14.268 * 86_400e3) as i64);
+        let activity = source.activity_at(&TestData, when).unwrap();
+        assert!((activity - 2.5e5).abs() < 1e-3);
+
+        assert_eq!(source.activity_at(&TestData, reference).unwrap(), 1e6);
+
+        let source = Source::new("H-3".parse().unwrap(), 1e6, reference);
+        assert!(source.activity_at(&TestData, when).is_err());
+    }
+}